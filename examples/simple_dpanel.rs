@@ -33,7 +33,7 @@ impl Default for MyApp {
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let dpanel = DynamicPanel::new("bla");
-        let dpanel = dpanel.with_panels(vec![
+        let dpanel = dpanel.with_panels([
             SinglePanelCfg::left().into(),
             SinglePanelCfg::bottom().into(),
         ]);
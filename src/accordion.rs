@@ -0,0 +1,100 @@
+//! Titled sections stacked inside a panel, each of which can be collapsed.
+
+use egui::{CollapsingHeader, Id, Ui};
+
+/// Collapsible sections stacked on top of each other, e.g. the tool groups of an inspector panel.
+/// The open state of every section is kept in egui's memory.
+///
+/// ```ignore
+/// Accordion::new("inspector").single_open(true).show(ui, |accordion| {
+///     accordion.section("Transform", |ui| ui.label("…"));
+///     accordion.section("Material", |ui| ui.label("…"));
+/// });
+/// ```
+pub struct Accordion {
+    id: Id,
+    single_open: bool,
+    default_open: bool,
+}
+
+impl Accordion {
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            single_open: false,
+            default_open: false,
+        }
+    }
+
+    /// Opening a section closes the others.
+    pub fn single_open(mut self, single_open: bool) -> Self {
+        self.single_open = single_open;
+        self
+    }
+
+    /// Whether sections start out open. In single-open mode only the first section does.
+    pub fn default_open(mut self, default_open: bool) -> Self {
+        self.default_open = default_open;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_sections: impl FnOnce(&mut AccordionUi<'_>) -> R) -> R {
+        let mut accordion = AccordionUi {
+            ui,
+            accordion: self,
+            first: true,
+        };
+        add_sections(&mut accordion)
+    }
+
+    /// The open section in single-open mode: `None` before any was toggled, `Some(None)` if all are closed.
+    fn open_section(&self, ui: &Ui) -> Option<Option<Id>> {
+        ui.data_mut(|d| d.get_persisted(self.id.with("__open_section")))
+    }
+}
+
+/// Adds sections to an [`Accordion`].
+pub struct AccordionUi<'u> {
+    ui: &'u mut Ui,
+    accordion: Accordion,
+    first: bool,
+}
+
+impl AccordionUi<'_> {
+    /// Adds a section, `add_contents` is only called while it is open.
+    pub fn section<R>(
+        &mut self,
+        title: &str,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<R> {
+        let accordion = &self.accordion;
+        let id = accordion.id.with(title);
+        let default_open = accordion.default_open && (!accordion.single_open || self.first);
+        self.first = false;
+        let open = if accordion.single_open {
+            accordion
+                .open_section(self.ui)
+                .map_or(default_open, |open| open == Some(id))
+        } else {
+            self.ui
+                .data_mut(|d| d.get_persisted(id))
+                .unwrap_or(default_open)
+        };
+
+        let response = CollapsingHeader::new(title)
+            .id_salt(id)
+            .open(Some(open))
+            .show(self.ui, add_contents);
+        if response.header_response.clicked() {
+            self.ui.data_mut(|d| {
+                if accordion.single_open {
+                    let open_section = (!open).then_some(id);
+                    d.insert_persisted(accordion.id.with("__open_section"), open_section);
+                } else {
+                    d.insert_persisted(id, !open);
+                }
+            });
+        }
+        response.body_returned
+    }
+}
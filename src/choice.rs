@@ -0,0 +1,158 @@
+//! Building blocks for choice functions, for layouts that depend on several conditions.
+//!
+//! ```ignore
+//! use egui_dpanel::choice::{is_touch, min_width, portrait};
+//!
+//! let panel = DynamicPanel::new("sidebar")
+//!     .with_panels([left.into(), bottom.into(), sheet.into()])
+//!     .with_rules([
+//!         min_width(600.0).and(!is_touch()).then_index(0),
+//!         portrait().or(is_touch()).then_index(2),
+//!     ])
+//!     .with_fallback_index(1);
+//! ```
+//!
+//! Conditions look at the [screen](crate::breakpoint_rect), like the choice functions of the presets. The first
+//! rule whose condition holds chooses the config, see [`crate::DynamicPanel::with_rules`].
+
+use egui::Context;
+
+type ConditionFn = dyn Fn(&Context) -> bool;
+
+/// A condition on the context, combinable with [`Self::and`], [`Self::or`] and `!`.
+pub struct Condition(Box<ConditionFn>);
+
+impl Condition {
+    pub fn new(f: impl Fn(&Context) -> bool + 'static) -> Self {
+        Self(Box::new(f))
+    }
+
+    /// Whether the condition holds for `ctx`.
+    pub fn holds(&self, ctx: &Context) -> bool {
+        (self.0)(ctx)
+    }
+
+    pub fn and(self, other: Condition) -> Self {
+        Self::new(move |ctx| self.holds(ctx) && other.holds(ctx))
+    }
+
+    pub fn or(self, other: Condition) -> Self {
+        Self::new(move |ctx| self.holds(ctx) || other.holds(ctx))
+    }
+
+    /// Rule choosing the config at `index` while the condition holds.
+    pub fn then_index(self, index: usize) -> Rule {
+        Rule {
+            condition: self,
+            index,
+        }
+    }
+}
+
+impl std::ops::Not for Condition {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::new(move |ctx| !self.holds(ctx))
+    }
+}
+
+/// A condition and the index of the config it chooses.
+pub struct Rule {
+    condition: Condition,
+    index: usize,
+}
+
+impl Rule {
+    /// The index of the config if the condition holds for `ctx`.
+    pub fn choose(&self, ctx: &Context) -> Option<usize> {
+        self.condition.holds(ctx).then_some(self.index)
+    }
+}
+
+/// Index chosen by the first rule whose condition holds, `None` if there is none.
+pub fn choose(rules: &[Rule], ctx: &Context) -> Option<usize> {
+    rules.iter().find_map(|rule| rule.choose(ctx))
+}
+
+/// The screen is at least `width` points wide.
+pub fn min_width(width: f32) -> Condition {
+    Condition::new(move |ctx| crate::breakpoint_rect(ctx).width() >= width)
+}
+
+/// The screen is less than `width` points wide.
+pub fn max_width(width: f32) -> Condition {
+    !min_width(width)
+}
+
+/// The screen is at least `height` points high.
+pub fn min_height(height: f32) -> Condition {
+    Condition::new(move |ctx| crate::breakpoint_rect(ctx).height() >= height)
+}
+
+/// The screen is less than `height` points high.
+pub fn max_height(height: f32) -> Condition {
+    !min_height(height)
+}
+
+/// The screen is higher than it is wide.
+pub fn portrait() -> Condition {
+    Condition::new(|ctx| {
+        let screen = crate::breakpoint_rect(ctx);
+        screen.height() > screen.width()
+    })
+}
+
+/// The screen is at least as wide as it is high.
+pub fn landscape() -> Condition {
+    !portrait()
+}
+
+/// egui has seen a touch screen.
+pub fn is_touch() -> Condition {
+    Condition::new(|ctx| ctx.input(|i| i.has_touch_screen()))
+}
+
+/// Always holds, for a last rule catching everything else.
+pub fn always() -> Condition {
+    Condition::new(|_| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LayoutProbe;
+    use crate::{DynamicPanel, SinglePanelCfg};
+
+    fn sidebar<'a>() -> DynamicPanel<'a> {
+        DynamicPanel::new("sidebar")
+            .with_panels([
+                SinglePanelCfg::left().into(),
+                SinglePanelCfg::bottom().into(),
+                SinglePanelCfg::top().into(),
+            ])
+            .with_rules([
+                min_width(600.0).and(!portrait()).then_index(0),
+                portrait().or(max_height(200.0)).then_index(2),
+            ])
+            .with_fallback_index(1)
+    }
+
+    /// Index `sidebar` shows on a screen of `width`x`height` points, `None` if it shows nothing.
+    fn index_at(width: f32, height: f32) -> Option<usize> {
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(width, height), |ctx| {
+            sidebar().show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.shown(&sidebar()).map(|shown| shown.index)
+    }
+
+    #[test]
+    fn the_first_rule_that_holds_chooses() {
+        assert_eq!(index_at(800.0, 600.0), Some(0));
+        assert_eq!(index_at(400.0, 800.0), Some(2));
+        assert_eq!(index_at(1000.0, 150.0), Some(0));
+        assert_eq!(index_at(500.0, 150.0), Some(2));
+        assert_eq!(index_at(500.0, 400.0), Some(1));
+    }
+}
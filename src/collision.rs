@@ -0,0 +1,148 @@
+//! Debug-build detection of panels sharing an Id.
+//!
+//! egui only notices two panels with the same Id if they end up at different places, and even then it can't tell
+//! which panels are involved. Every panel registers its name here when its content is shown, so a second panel
+//! with the same Id in the same pass is reported by name. Raw egui panels don't register, but they store their
+//! `PanelState` under the same Id: a state that changed after a panel of this crate was shown is reported at the
+//! end of the pass.
+
+use egui::containers::panel::PanelState;
+use egui::{Context, Id, Rect};
+use std::sync::Arc;
+
+#[derive(Clone, Default)]
+struct Registry {
+    /// Pass the entries were registered in.
+    pass: u64,
+    /// Ids and names of the panels shown in `pass`.
+    used: Vec<(Id, String)>,
+    /// Egui panel Ids, names and `PanelState` rects of the panels shown in `pass`, see [`shown`].
+    states: Vec<(Id, String, Rect)>,
+    /// Collisions that were already logged, so the log isn't flooded every frame.
+    warned: Vec<Id>,
+}
+
+impl Registry {
+    fn load(ctx: &Context) -> Self {
+        let id = registry_id(ctx);
+        let mut registry: Self = ctx.data(|d| d.get_temp(id)).unwrap_or_default();
+        let pass = ctx.cumulative_pass_nr();
+        if registry.pass != pass {
+            registry.pass = pass;
+            registry.used.clear();
+            registry.states.clear();
+        }
+        registry
+    }
+
+    fn store(self, ctx: &Context) {
+        let id = registry_id(ctx);
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    /// Logs `message` the first time a collision at `id` is found.
+    fn warn(&mut self, id: Id, message: &str) {
+        if !self.warned.contains(&id) {
+            self.warned.push(id);
+            log::warn!("{message}");
+        }
+    }
+}
+
+fn registry_id(ctx: &Context) -> Id {
+    Id::new("egui_dpanel_used_ids").with(ctx.viewport_id())
+}
+
+/// Registers the panel `name` with `id`, warning if another panel used the same Id in this pass. `rect` is where
+/// the warning is painted. Only active while [`egui::Options::warn_on_id_clash`] is set, which is the default in
+/// debug builds.
+pub(crate) fn register(ctx: &Context, id: Id, name: &str, rect: Rect) {
+    if !ctx.options(|o| o.warn_on_id_clash) {
+        return;
+    }
+    let mut registry = Registry::load(ctx);
+    let previous = registry
+        .used
+        .iter()
+        .find(|(used, _)| *used == id)
+        .map(|(_, previous)| previous.clone());
+    match previous {
+        Some(previous) => {
+            let message = format!(
+                "DynamicPanel \"{name}\" uses the same Id as \"{previous}\", give one of them another name"
+            );
+            registry.warn(id, &message);
+            ctx.debug_painter().error(rect.left_top(), message);
+        }
+        None => registry.used.push((id, name.to_owned())),
+    }
+    registry.store(ctx);
+}
+
+/// Remembers the `PanelState` the egui panel `id` of the panel `name` was left with, to report panels that aren't
+/// shown by this crate and overwrite it later in the pass.
+pub(crate) fn shown(ctx: &Context, id: Id, name: &str) {
+    if !ctx.options(|o| o.warn_on_id_clash) {
+        return;
+    }
+    let Some(state) = PanelState::load(ctx, id) else {
+        return;
+    };
+    let check_id = Id::new("egui_dpanel_state_check");
+    if !ctx.data(|d| d.get_temp::<bool>(check_id)).unwrap_or(false) {
+        ctx.data_mut(|d| d.insert_temp(check_id, true));
+        ctx.on_end_pass("egui_dpanel_state_check", Arc::new(check_states));
+    }
+    let mut registry = Registry::load(ctx);
+    registry.states.retain(|(shown, _, _)| *shown != id);
+    registry.states.push((id, name.to_owned(), state.rect));
+    registry.store(ctx);
+}
+
+/// Reports the panels whose `PanelState` changed since they were shown in this pass.
+fn check_states(ctx: &Context) {
+    let mut registry = Registry::load(ctx);
+    let states = std::mem::take(&mut registry.states);
+    for (id, name, rect) in &states {
+        if PanelState::load(ctx, *id).is_some_and(|state| state.rect != *rect) {
+            let message = format!(
+                "An egui panel not shown by egui_dpanel uses the same Id as DynamicPanel \"{name}\" and \
+                 overwrote its state, give one of them another Id"
+            );
+            registry.warn(*id, &message);
+            ctx.debug_painter().error(rect.left_top(), message);
+        }
+    }
+    registry.states = states;
+    registry.store(ctx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LayoutProbe;
+    use crate::{DynamicPanel, SinglePanelCfg};
+
+    /// Ids reported after a few frames of a sidebar, and of a raw egui panel with the same Id if `raw`.
+    fn warned(raw: bool) -> Vec<Id> {
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            let sidebar = DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().exact_size(200.0).into()])
+                .with_choice_function(|_| 0);
+            sidebar.show_dynamic(ctx, |ui| ui.label("…"));
+            if raw {
+                egui::SidePanel::right(sidebar.id())
+                    .exact_width(100.0)
+                    .show(ctx, |ui| ui.label("…"));
+            }
+        });
+        Registry::load(probe.context()).warned
+    }
+
+    #[test]
+    fn raw_egui_panels_with_the_same_id_are_reported() {
+        assert_eq!(warned(false), []);
+        assert_eq!(warned(true), [Id::new("sidebar")]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Controlling a panel from anywhere in the app, including other threads, through a queue of commands.
+
+use crate::state::DynamicPanelState;
+use crate::{DynamicPanel, Side};
+use egui::{Context, Id};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Something to do with a panel, see [`PanelCommands`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PanelCommand {
+    Expand,
+    Collapse,
+    Toggle,
+    /// Show the config at this index regardless of the choice function, or follow it again with `None`.
+    /// See [`DynamicPanel::pin_index`]. Indices without a config are ignored.
+    SetIndex(Option<usize>),
+    /// Move the docked panel to `side`: to the opposite side of its current config like the separator menu does,
+    /// or to the first config docked at `side` otherwise. That config is shown until the choice function switches
+    /// to another one, it is not pinned.
+    MoveToSide(Side),
+}
+
+/// Both ends of the queue of a panel, kept in egui's memory so the panel finds it again every frame.
+#[derive(Clone)]
+struct Queue {
+    sender: Sender<PanelCommand>,
+    receiver: Arc<Mutex<Receiver<PanelCommand>>>,
+}
+
+/// Clonable handle enqueueing commands for a panel, obtained with [`DynamicPanel::commands`]. The commands are
+/// applied at the start of the next `show_dynamic*` call of the panel, sending one requests a repaint.
+#[derive(Clone)]
+pub struct PanelCommands {
+    ctx: Context,
+    sender: Sender<PanelCommand>,
+}
+
+impl PanelCommands {
+    /// Enqueues `command`.
+    pub fn send(&self, command: PanelCommand) {
+        if self.sender.send(command).is_ok() {
+            self.ctx.request_repaint();
+        }
+    }
+
+    pub fn expand(&self) {
+        self.send(PanelCommand::Expand);
+    }
+
+    pub fn collapse(&self) {
+        self.send(PanelCommand::Collapse);
+    }
+
+    pub fn toggle(&self) {
+        self.send(PanelCommand::Toggle);
+    }
+
+    pub fn set_index(&self, index: Option<usize>) {
+        self.send(PanelCommand::SetIndex(index));
+    }
+
+    pub fn move_to_side(&self, side: Side) {
+        self.send(PanelCommand::MoveToSide(side));
+    }
+}
+
+fn queue_id(panel: Id) -> Id {
+    panel.with("__commands")
+}
+
+/// The command handle of the panel with `id`, creating its queue if there is none yet.
+pub(crate) fn handle(ctx: &Context, id: Id) -> PanelCommands {
+    let sender = ctx.data_mut(|d| {
+        d.get_temp_mut_or_insert_with(queue_id(id), || {
+            let (sender, receiver) = channel();
+            Queue {
+                sender,
+                receiver: Arc::new(Mutex::new(receiver)),
+            }
+        })
+        .sender
+        .clone()
+    });
+    PanelCommands {
+        ctx: ctx.clone(),
+        sender,
+    }
+}
+
+/// Applies the commands enqueued for `panel` since the last call.
+pub(crate) fn apply(ctx: &Context, panel: &DynamicPanel<'_>) {
+    let Some(queue) = ctx.data(|d| d.get_temp::<Queue>(queue_id(panel.id()))) else {
+        return;
+    };
+    let commands: Vec<PanelCommand> = queue.receiver.lock().unwrap().try_iter().collect();
+    for command in commands {
+        match command {
+            PanelCommand::Expand => panel.set_expanded(ctx, true),
+            PanelCommand::Collapse => panel.set_expanded(ctx, false),
+            PanelCommand::Toggle => panel.toggle(ctx),
+            PanelCommand::SetIndex(index) => panel.pin_index(ctx, index),
+            PanelCommand::MoveToSide(side) => move_to_side(ctx, panel, side),
+        }
+    }
+}
+
+fn move_to_side(ctx: &Context, panel: &DynamicPanel<'_>, side: Side) {
+    let id = panel.id();
+    let mut state = DynamicPanelState::load(ctx, id);
+    let current = state
+        .current_index()
+        .and_then(|index| panel.docked(index))
+        .map(|cfg| cfg.side);
+    if current == Some(side) || current == Some(side.opposite()) {
+        state.flipped = current != Some(side);
+        state.store(ctx, id);
+        return;
+    }
+    match (0..panel.panels().len())
+        .find(|&index| panel.docked(index).is_some_and(|cfg| cfg.side == side))
+    {
+        Some(index) => {
+            state.flipped = false;
+            state.store(ctx, id);
+            panel.prefer_index(ctx, index);
+        }
+        None => log::warn!(
+            "DynamicPanel `{}` has no config docked at {side:?}",
+            panel.name()
+        ),
+    }
+}
@@ -0,0 +1,190 @@
+//! Interactive gallery of what the panels can do, to explore the behaviors and copy settings from.
+//!
+//! [`Demo::show`] puts the panels of a preset around the app's central panel, [`demo_ui`] embeds a smaller demo
+//! in any Ui, e.g. a window.
+
+use crate::{
+    breakpoint_rect, set_screen_preview, CollapsiblePanelCfg, DynamicPanel, PanelCfg,
+    ScreenPreview, SideCfg, TopBottomCfg,
+};
+use egui::{Context, Ui};
+
+/// Layout shown by [`Demo::show`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DemoLayout {
+    /// A collapsible sidebar that becomes a bottom bar below the breakpoint.
+    Sidebar,
+    /// [`DynamicPanel::status_bar`].
+    StatusBar,
+    /// [`DynamicPanel::dialog`].
+    Dialog,
+    /// [`DynamicPanel::fab_sheet`].
+    FabSheet,
+}
+
+/// Settings of the demo, changed with the controls of [`Self::ui`].
+#[derive(Clone, Debug)]
+pub struct Demo {
+    pub layout: DemoLayout,
+    /// Screen width (height for the status bar) below which the panels switch to their compact config.
+    pub breakpoint: f32,
+    pub animated: bool,
+    pub interpolate_sizes: bool,
+    /// Size of the simulated screen, see [`set_screen_preview`].
+    pub preview: Option<egui::Vec2>,
+    /// Whether the embedded panel of [`Self::ui`] is expanded.
+    pub expanded: bool,
+}
+
+impl Default for Demo {
+    fn default() -> Self {
+        Self {
+            layout: DemoLayout::Sidebar,
+            breakpoint: 600.0,
+            animated: true,
+            interpolate_sizes: false,
+            preview: None,
+            expanded: true,
+        }
+    }
+}
+
+impl Demo {
+    /// Shows the panels of [`Self::layout`] and a window with the controls. Has to be called before the app's
+    /// central panel, like any panel.
+    pub fn show(&mut self, ctx: &Context) {
+        egui::Window::new("egui_dpanel demo").show(ctx, |ui| self.controls(ui));
+        let breakpoint = self.breakpoint;
+        match self.layout {
+            DemoLayout::Sidebar => {
+                let sidebar = DynamicPanel::new("demo_sidebar")
+                    .with_panels([
+                        PanelCfg::Collapsible(CollapsiblePanelCfg::new(
+                            SideCfg::left().exact_width(32.0).resizable(false).into(),
+                            SideCfg::left().width_range(150.0, 400.0).into(),
+                        )),
+                        TopBottomCfg::bottom().default_height(120.0).into(),
+                    ])
+                    .with_threshold_function(move |ctx| breakpoint_rect(ctx).width() < breakpoint)
+                    .animated(self.animated)
+                    .animate_index_changes(true)
+                    .interpolate_sizes(self.interpolate_sizes);
+                sidebar.show_dynamic_collapsible(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        sidebar.collapse_button(ui);
+                        sidebar.tear_off_handle(ui);
+                    });
+                    ui.label(
+                        "Drag the separator, collapse the panel, tear it off or resize the window.",
+                    );
+                });
+            }
+            DemoLayout::StatusBar => {
+                let status =
+                    DynamicPanel::status_bar("demo_status", breakpoint).animated(self.animated);
+                status.show_dynamic_animated(ctx, true, |ui| ui.label("Everything is fine."));
+            }
+            DemoLayout::Dialog => {
+                DynamicPanel::dialog("demo_dialog", "Settings", breakpoint)
+                    .animated(self.animated)
+                    .show_dynamic_collapsible(ctx, |ui| {
+                        ui.label("Close with the button, Escape or a click outside.");
+                    });
+            }
+            DemoLayout::FabSheet => {
+                DynamicPanel::fab_sheet("demo_fab", "☰", breakpoint)
+                    .animated(self.animated)
+                    .show_dynamic_collapsible(ctx, |ui| {
+                        ui.label("Navigation");
+                    });
+            }
+        }
+    }
+
+    /// The controls and an embedded panel that moves from the left to the bottom when the Ui gets narrower than
+    /// the breakpoint.
+    pub fn ui(&mut self, ui: &mut Ui) {
+        self.controls(ui);
+        ui.separator();
+        ui.checkbox(&mut self.expanded, "Expanded");
+        let ctx = ui.ctx().clone();
+        let breakpoint = self.breakpoint;
+        let panel = DynamicPanel::new("demo_embedded")
+            .with_panels([
+                SideCfg::left().default_width(120.0).into(),
+                TopBottomCfg::bottom().default_height(60.0).into(),
+            ])
+            .with_rect_choice_function(move |rect| usize::from(rect.width() < breakpoint))
+            .animated(self.animated)
+            .interpolate_sizes(self.interpolate_sizes);
+        let size = egui::vec2(ui.available_width(), 200.0);
+        ui.allocate_ui(size, |ui| {
+            panel.show_dynamic_animated_inside(&ctx, ui, self.expanded, |ui| {
+                ui.label("Panel");
+            });
+            egui::CentralPanel::default().show_inside(ui, |ui| {
+                ui.label("Central panel");
+            });
+        });
+    }
+
+    fn controls(&mut self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(&mut self.layout, DemoLayout::Sidebar, "Sidebar");
+            ui.selectable_value(&mut self.layout, DemoLayout::StatusBar, "Status bar");
+            ui.selectable_value(&mut self.layout, DemoLayout::Dialog, "Dialog");
+            ui.selectable_value(&mut self.layout, DemoLayout::FabSheet, "FAB sheet");
+        });
+        ui.add(egui::Slider::new(&mut self.breakpoint, 200.0..=1200.0).text("Breakpoint"));
+        ui.checkbox(&mut self.animated, "Animated");
+        ui.checkbox(&mut self.interpolate_sizes, "Interpolate sizes");
+        // Panels keep their state in egui's memory, another panel with the same name controls it.
+        let panel = DynamicPanel::new(self.panel_name());
+        ui.horizontal(|ui| {
+            if ui.button("Expand").clicked() {
+                panel.set_expanded(ui.ctx(), true);
+            }
+            if ui.button("Collapse").clicked() {
+                panel.set_expanded(ui.ctx(), false);
+            }
+            if ui.button("Reveal").clicked() {
+                panel.reveal(ui.ctx());
+            }
+        });
+        let mut previewing = self.preview.is_some();
+        ui.checkbox(&mut previewing, "Preview a phone screen");
+        match (previewing, &mut self.preview) {
+            (true, Some(size)) => {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut size.x).range(100.0..=2000.0));
+                    ui.label("×");
+                    ui.add(egui::DragValue::new(&mut size.y).range(100.0..=2000.0));
+                });
+            }
+            (true, None) => self.preview = Some(egui::vec2(390.0, 844.0)),
+            (false, _) => self.preview = None,
+        }
+        let preview = self.preview.map(|size| ScreenPreview {
+            size,
+            show_boundary: true,
+        });
+        set_screen_preview(ui.ctx(), preview);
+    }
+
+    fn panel_name(&self) -> &'static str {
+        match self.layout {
+            DemoLayout::Sidebar => "demo_sidebar",
+            DemoLayout::StatusBar => "demo_status",
+            DemoLayout::Dialog => "demo_dialog",
+            DemoLayout::FabSheet => "demo_fab",
+        }
+    }
+}
+
+/// Shows [`Demo::ui`] with settings kept in egui's memory.
+pub fn demo_ui(ui: &mut Ui) {
+    let id = ui.id().with("egui_dpanel_demo");
+    let mut demo: Demo = ui.data_mut(|d| d.get_temp(id)).unwrap_or_default();
+    demo.ui(ui);
+    ui.data_mut(|d| d.insert_temp(id, demo));
+}
@@ -0,0 +1,141 @@
+//! Keeps keyboard focus inside a modal panel while it is open.
+//!
+//! egui has no notion of focus scopes, so the trap surrounds the panel content with two invisible
+//! focusable sentinels. Tabbing onto one of them means focus is about to leave the panel, and it is
+//! sent to the other end instead. The first and last real widgets are learned on the way, so after
+//! one round trip the sentinels are skipped entirely.
+
+use egui::{Context, Id, Rect, Sense, Ui, Vec2};
+
+#[derive(Clone, Default)]
+struct FocusTrap {
+    /// Pass in which the content was last shown.
+    open_pass: Option<u64>,
+    /// Widget that had focus before the panel opened.
+    restore: Option<Id>,
+    /// First focusable widget of the content, once known.
+    first: Option<Id>,
+    /// Last focusable widget of the content, once known.
+    last: Option<Id>,
+    /// Focused widget at the end of the previous pass.
+    previous: Option<Id>,
+    /// Sentinel we moved the focus to ourselves, so it is not mistaken for the user leaving the panel.
+    redirected: Option<Id>,
+}
+
+impl FocusTrap {
+    fn load(ctx: &Context, id: Id) -> Self {
+        ctx.data(|d| d.get_temp(id)).unwrap_or_default()
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+}
+
+fn trap_id(panel_id: Id) -> Id {
+    panel_id.with("__focus_trap")
+}
+
+/// Shows `content` with Tab-navigation cycling inside of it.
+pub(crate) fn trap<R>(ui: &mut Ui, panel_id: Id, content: impl FnOnce(&mut Ui) -> R) -> R {
+    let ctx = ui.ctx().clone();
+    let id = trap_id(panel_id);
+    let start_id = id.with("start");
+    let end_id = id.with("end");
+    let mut trap = FocusTrap::load(&ctx, id);
+
+    if trap.open_pass.is_none() {
+        trap.restore = ctx.memory(|m| m.focused());
+        trap.redirected = Some(start_id);
+        ctx.memory_mut(|m| m.request_focus(start_id));
+    }
+    trap.open_pass = Some(ctx.cumulative_pass_nr());
+
+    ui.interact(
+        Rect::from_min_size(ui.cursor().min, Vec2::ZERO),
+        start_id,
+        Sense::focusable_noninteractive(),
+    );
+    let inner = content(ui);
+    ui.interact(
+        Rect::from_min_size(ui.cursor().min, Vec2::ZERO),
+        end_id,
+        Sense::focusable_noninteractive(),
+    );
+
+    let panel_rect = ui.max_rect();
+    let is_inside = |w: Id| {
+        w != start_id
+            && w != end_id
+            && ctx
+                .read_response(w)
+                .is_some_and(|r| panel_rect.intersects(r.rect))
+    };
+    let previous_inside = trap.previous.filter(|&w| is_inside(w));
+
+    let mut redirect = None;
+    match ctx.memory(|m| m.focused()) {
+        Some(w) if Some(w) == trap.redirected => {}
+        Some(w) if w == end_id => {
+            // Tabbed past the last widget.
+            trap.last = previous_inside.or(trap.last);
+            redirect = Some(trap.first.unwrap_or(start_id));
+        }
+        Some(w) if w == start_id => {
+            // Shift-tabbed past the first widget.
+            trap.first = previous_inside.or(trap.first);
+            redirect = Some(trap.last.unwrap_or(end_id));
+        }
+        Some(w) if is_inside(w) => {
+            if trap.previous == Some(start_id) {
+                trap.first = Some(w);
+            } else if trap.previous == Some(end_id) {
+                trap.last = Some(w);
+            }
+            trap.redirected = None;
+        }
+        Some(_) => {
+            // Focus escaped the panel, e.g. by clicking elsewhere.
+            redirect = Some(previous_inside.unwrap_or(start_id));
+        }
+        None => {}
+    }
+
+    if let Some(target) = redirect {
+        ctx.memory_mut(|m| m.request_focus(target));
+        trap.redirected = (target == start_id || target == end_id).then_some(target);
+    }
+    trap.previous = ctx.memory(|m| m.focused());
+    trap.store(&ctx, id);
+    inner
+}
+
+/// Releases the trap if the content was not shown in this pass, returning focus to where it was before.
+pub(crate) fn release_if_closed(ctx: &Context, panel_id: Id) {
+    let trap = FocusTrap::load(ctx, trap_id(panel_id));
+    if trap
+        .open_pass
+        .is_some_and(|open_pass| open_pass != ctx.cumulative_pass_nr())
+    {
+        release(ctx, panel_id);
+    }
+}
+
+/// Releases the trap, returning focus to where it was before, e.g. because the panel was just closed.
+pub(crate) fn release(ctx: &Context, panel_id: Id) {
+    let id = trap_id(panel_id);
+    let trap = FocusTrap::load(ctx, id);
+    if trap.open_pass.is_none() {
+        return;
+    }
+    ctx.memory_mut(|m| match trap.restore {
+        Some(restore) => m.request_focus(restore),
+        None => {
+            if let Some(focused) = trap.previous {
+                m.surrender_focus(focused);
+            }
+        }
+    });
+    ctx.data_mut(|d| d.remove::<FocusTrap>(id));
+}
@@ -0,0 +1,419 @@
+//! Panels sharing the screen, coordinated so that together they don't crowd out the central area.
+
+use crate::observer::{self, LayoutObserver};
+use crate::state::DynamicPanelState;
+use crate::{DynamicPanel, SinglePanelCfg};
+use egui::containers::panel::PanelState;
+use egui::{Context, Rect, Style};
+use std::sync::Arc;
+
+/// Group of panels with priorities. When the docked panels of the group need more space than the screen has
+/// (keeping a minimum for the central area), the panels with the lowest priority are hidden until there is enough
+/// room again. Panels expanded from a [`crate::PanelToggleBar`] are never hidden, the group makes room for them
+/// instead. Panels on the left/right compete for the width, panels on the top/bottom for the height. With
+/// [`Self::reserve_central_min`], the panels are shrunk toward their minimum size before any is hidden.
+///
+/// Hidden panels count as collapsed, so they have to be shown with
+/// [`DynamicPanel::show_dynamic_collapsible`] (or read [`DynamicPanel::is_expanded`]) for the group to have an
+/// effect. Call [`Self::update`] every frame before showing them.
+///
+/// The group also links master and detail panels, see [`Self::with_detail`].
+pub struct PanelGroup<'p, 'a> {
+    members: Vec<(&'p DynamicPanel<'a>, i32)>,
+    details: Vec<(&'p DynamicPanel<'a>, &'p DynamicPanel<'a>)>,
+    central_min_width: f32,
+    central_min_height: f32,
+    shrink_to_fit: bool,
+    #[cfg(feature = "animation")]
+    animate_reordering: bool,
+    observers: Vec<Arc<dyn LayoutObserver>>,
+}
+
+impl Default for PanelGroup<'_, '_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'p, 'a> PanelGroup<'p, 'a> {
+    pub fn new() -> Self {
+        Self {
+            members: vec![],
+            details: vec![],
+            central_min_width: 0.0,
+            central_min_height: 0.0,
+            shrink_to_fit: false,
+            #[cfg(feature = "animation")]
+            animate_reordering: false,
+            observers: vec![],
+        }
+    }
+
+    /// Adds a panel, panels with higher priority are hidden last.
+    pub fn with_panel(mut self, panel: &'p DynamicPanel<'a>, priority: i32) -> Self {
+        self.members.push((panel, priority));
+        self
+    }
+
+    /// Expands `detail` when `master` reports a selection with [`DynamicPanel::report_selection`], and collapses
+    /// it when the selection is cleared. In between, the detail panel can still be collapsed and expanded by hand.
+    pub fn with_detail(
+        mut self,
+        master: &'p DynamicPanel<'a>,
+        detail: &'p DynamicPanel<'a>,
+    ) -> Self {
+        self.details.push((master, detail));
+        self
+    }
+
+    /// Width that has to remain for the central area, besides the left and right panels.
+    pub fn central_min_width(mut self, width: f32) -> Self {
+        self.central_min_width = width;
+        self
+    }
+
+    /// Height that has to remain for the central area, besides the top and bottom panels.
+    pub fn central_min_height(mut self, height: f32) -> Self {
+        self.central_min_height = height;
+        self
+    }
+
+    /// Guarantees the central area at least `width` points: when the left and right panels at their current sizes
+    /// would leave less, they are shrunk toward their minimum size, lowest priority first, and hidden once that
+    /// isn't enough. Top and bottom panels are shrunk the same way for [`Self::central_min_height`].
+    pub fn reserve_central_min(mut self, width: f32) -> Self {
+        self.central_min_width = width;
+        self.shrink_to_fit = true;
+        self
+    }
+
+    /// Let the content of the panels slide to its new place when a panel of the group is hidden or the panels are
+    /// shown in another order, instead of jumping there. Only the content slides, the panels take their new place
+    /// right away. Off by default, honors [`DynamicPanel::animated`] and the reduce motion preference.
+    #[cfg(feature = "animation")]
+    pub fn animate_reordering(mut self, animate: bool) -> Self {
+        self.animate_reordering = animate;
+        self
+    }
+
+    /// Registers an observer for the layout changes of all panels of the group, reported by [`Self::update`].
+    pub fn observer(mut self, observer: Arc<dyn LayoutObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Applies selections of master panels to their details, then decides which panels fit on the screen and
+    /// hides the others.
+    pub fn update(&self, ctx: &Context) {
+        for (master, detail) in &self.details {
+            let selection = DynamicPanelState::load(ctx, master.id()).selection;
+            let mut state = DynamicPanelState::load(ctx, detail.id());
+            if selection.is_some() && selection != state.linked_selection {
+                state.collapsed = selection == Some(false);
+                state.auto_expanded = !state.collapsed;
+                state.linked_selection = selection;
+                state.store(ctx, detail.id());
+            }
+        }
+
+        let screen = crate::screen_rect(ctx);
+        let style = ctx.style();
+        let mut width = self.central_min_width;
+        let mut height = self.central_min_height;
+        let mut members: Vec<_> = self
+            .members
+            .iter()
+            .map(|&(panel, priority)| {
+                let mut state = DynamicPanelState::load(ctx, panel.id());
+                state.user_shown &= !state.collapsed;
+                (panel, priority, state)
+            })
+            .collect();
+        members
+            .sort_by_key(|(_, priority, state)| std::cmp::Reverse((state.user_shown, *priority)));
+        let mut shown = vec![];
+        let mut states = vec![];
+        for (panel, _, mut state) in members {
+            let cfg = state.current_index().and_then(|index| panel.docked(index));
+            state.auto_hidden = match cfg {
+                Some(cfg) if !state.collapsed => {
+                    let (used, available) = if cfg.side.is_lr() {
+                        (&mut width, screen.width())
+                    } else {
+                        (&mut height, screen.height())
+                    };
+                    let size = cfg.smallest_size(&style);
+                    // A panel that doesn't fit leaves its space to smaller panels of lower priority.
+                    let fits = *used + size <= available || state.user_shown;
+                    if fits {
+                        *used += size;
+                        shown.push((panel, cfg));
+                    }
+                    !fits
+                }
+                // Collapsed panels and overlays don't take space.
+                _ => false,
+            };
+            states.push((panel, state));
+        }
+        #[cfg(feature = "animation")]
+        self.track_reordering(ctx, &shown, &mut states);
+        for (panel, state) in states {
+            state.store(ctx, panel.id());
+        }
+        if self.shrink_to_fit {
+            self.shrink(ctx, &style, screen, &shown);
+        }
+
+        let mut observed = vec![];
+        let panels = self.members.iter().map(|&(panel, _)| panel);
+        let details = self
+            .details
+            .iter()
+            .flat_map(|&(master, detail)| [master, detail]);
+        for panel in panels.chain(details) {
+            if !observed.contains(&panel.id()) {
+                observed.push(panel.id());
+                observer::notify(
+                    ctx,
+                    panel,
+                    panel.id().with("__group_observed"),
+                    &self.observers,
+                );
+            }
+        }
+    }
+
+    /// Marks the panels as reordered when the `shown` panels, their sides or their order changed since the last
+    /// update, so their content slides to the new place. See [`Self::animate_reordering`].
+    #[cfg(feature = "animation")]
+    fn track_reordering(
+        &self,
+        ctx: &Context,
+        shown: &[(&DynamicPanel<'a>, &SinglePanelCfg)],
+        states: &mut [(&DynamicPanel<'a>, DynamicPanelState)],
+    ) {
+        let layout: Vec<(egui::Id, crate::Side)> = shown
+            .iter()
+            .map(|(panel, cfg)| {
+                (
+                    panel.id(),
+                    DynamicPanel::shown_side(ctx, panel.id(), cfg.side),
+                )
+            })
+            .collect();
+        let members: Vec<_> = self.members.iter().map(|(panel, _)| panel.id()).collect();
+        let id = egui::Id::new("__dpanel_group_layout").with(members);
+        let reordered = ctx.data_mut(|d| {
+            let last = d.get_temp::<Vec<(egui::Id, crate::Side)>>(id);
+            d.insert_temp(id, layout.clone());
+            last.is_some_and(|last| last != layout)
+        });
+        for (_, state) in states {
+            state.slides = self.animate_reordering;
+            if reordered && self.animate_reordering {
+                state.reordered_pass = Some(ctx.cumulative_pass_nr());
+            }
+        }
+    }
+
+    /// Shrinks the `shown` panels, sorted by descending priority, until the central area keeps its minimum size.
+    fn shrink(
+        &self,
+        ctx: &Context,
+        style: &Style,
+        screen: Rect,
+        shown: &[(&DynamicPanel<'a>, &SinglePanelCfg)],
+    ) {
+        for lr in [true, false] {
+            let (central, available) = if lr {
+                (self.central_min_width, screen.width())
+            } else {
+                (self.central_min_height, screen.height())
+            };
+            let panels: Vec<_> = shown
+                .iter()
+                .filter(|(_, cfg)| cfg.side.is_lr() == lr)
+                .map(|&(panel, cfg)| {
+                    let id = panel.panel_id(ctx);
+                    (id, cfg, PanelState::load(ctx, id))
+                })
+                .collect();
+            let used: f32 = panels
+                .iter()
+                .map(|(_, cfg, state)| match state {
+                    Some(state) => cfg.side.rect_size(state.rect),
+                    None => cfg.smallest_size(style),
+                })
+                .sum();
+            let mut excess = central + used - available;
+            for (id, cfg, state) in panels.into_iter().rev() {
+                if excess <= 0.0 {
+                    break;
+                }
+                let Some(PanelState { mut rect }) = state else {
+                    continue;
+                };
+                let size = cfg.side.rect_size(rect);
+                let shrunk = (size - excess).max(cfg.smallest_size(style));
+                if shrunk < size {
+                    cfg.side.set_rect_size(&mut rect, shrunk);
+                    ctx.data_mut(|d| d.insert_persisted(id, PanelState { rect }));
+                    excess -= size - shrunk;
+                }
+            }
+        }
+    }
+
+    /// Whether the group currently hides `panel`.
+    pub fn is_hidden(&self, ctx: &Context, panel: &DynamicPanel) -> bool {
+        DynamicPanelState::load(ctx, panel.id()).auto_hidden
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LayoutProbe;
+
+    fn panel<'a>(name: &str, cfg: SinglePanelCfg) -> DynamicPanel<'a> {
+        DynamicPanel::new(name)
+            .with_panels([cfg.into()])
+            .with_choice_function(|_| 0)
+    }
+
+    /// Runs a few frames of a 500 points wide screen with panels of 300, 300 and 150 points, and returns which
+    /// of them the group hid.
+    fn hidden(setup: impl Fn(&Context, &[&DynamicPanel])) -> Vec<bool> {
+        let mut hidden = vec![];
+        LayoutProbe::new()
+            .frames(3)
+            .run(egui::vec2(500.0, 400.0), |ctx| {
+                let high = panel("high", SinglePanelCfg::left().exact_size(300.0));
+                let middle = panel("middle", SinglePanelCfg::right().exact_size(300.0));
+                let low = panel("low", SinglePanelCfg::right().exact_size(150.0));
+                let panels = [&high, &middle, &low];
+                setup(ctx, &panels);
+                let group = PanelGroup::new()
+                    .with_panel(&high, 3)
+                    .with_panel(&middle, 2)
+                    .with_panel(&low, 1);
+                group.update(ctx);
+                for panel in panels {
+                    panel.show_dynamic_collapsible(ctx, |_| {});
+                }
+                hidden = panels.iter().map(|p| group.is_hidden(ctx, p)).collect();
+            });
+        hidden
+    }
+
+    #[test]
+    fn panels_that_dont_fit_leave_their_space_to_smaller_ones() {
+        assert_eq!(hidden(|_, _| {}), [false, true, false]);
+    }
+
+    #[test]
+    fn panels_shown_by_the_user_are_never_hidden() {
+        let shown = hidden(|ctx, panels| {
+            let mut state = DynamicPanelState::load(ctx, panels[1].id());
+            state.user_shown = true;
+            state.store(ctx, panels[1].id());
+        });
+        assert_eq!(shown, [true, false, false]);
+    }
+
+    /// Widths of a left and a right panel of 300 points each that shrink to 100 points on a 600 points wide screen,
+    /// `None` for a hidden panel. The right one has the lower priority. The group keeps 200 points for the central
+    /// area, only hiding panels unless `reserve`.
+    fn widths(reserve: bool) -> Vec<Option<f32>> {
+        let mut widths = vec![];
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(600.0, 400.0), |ctx| {
+            let high = panel(
+                "high",
+                SinglePanelCfg::left()
+                    .size_range(100.0, 300.0)
+                    .default_size(300.0),
+            );
+            let low = panel(
+                "low",
+                SinglePanelCfg::right()
+                    .size_range(100.0, 300.0)
+                    .default_size(300.0),
+            );
+            let group = if reserve {
+                PanelGroup::new().reserve_central_min(200.0)
+            } else {
+                PanelGroup::new().central_min_width(200.0)
+            };
+            group.with_panel(&high, 2).with_panel(&low, 1).update(ctx);
+            widths = [&high, &low]
+                .iter()
+                .map(|panel| {
+                    let shown = panel.show_dynamic_collapsible(ctx, |ui| {
+                        ui.allocate_space(ui.available_size());
+                    });
+                    shown.map(|_| {
+                        PanelState::load(ctx, panel.panel_id(ctx))
+                            .unwrap()
+                            .rect
+                            .width()
+                    })
+                })
+                .collect();
+        });
+        widths
+    }
+
+    #[test]
+    fn reserving_the_central_area_shrinks_the_panels() {
+        assert_eq!(widths(false), [Some(300.0), Some(300.0)]);
+        assert_eq!(widths(true), [Some(300.0), Some(100.0)]);
+    }
+
+    /// Left edge of the content of a panel docked right of another one, in frames 10 ms apart after that one
+    /// collapsed.
+    #[cfg(feature = "animation")]
+    fn content_left_after_collapse(animate: bool) -> Vec<f32> {
+        let ctx = Context::default();
+        let left = std::cell::Cell::new(0.0);
+        let run = |time: f64| {
+            let input = egui::RawInput {
+                screen_rect: Some(Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(800.0, 600.0),
+                )),
+                time: Some(time),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                // The outer panel disappears at once, so the inner one jumps.
+                let outer =
+                    panel("outer", SinglePanelCfg::left().exact_size(200.0)).animated(false);
+                let inner = panel("inner", SinglePanelCfg::left().exact_size(100.0));
+                let group = PanelGroup::new()
+                    .with_panel(&outer, 2)
+                    .with_panel(&inner, 1)
+                    .animate_reordering(animate);
+                group.update(ctx);
+                outer.show_dynamic_collapsible(ctx, |_| {});
+                inner.show_dynamic_collapsible(ctx, |ui| left.set(ui.max_rect().left()));
+            });
+            left.get()
+        };
+        run(0.0);
+        run(1.0);
+        panel("outer", SinglePanelCfg::left()).set_expanded(&ctx, false);
+        (0..20).map(|i| run(2.0 + f64::from(i) * 0.01)).collect()
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn content_slides_to_its_new_place() {
+        let slid = content_left_after_collapse(true);
+        assert!(slid[0] > 50.0);
+        assert!(slid.last().unwrap() < &20.0);
+        let jumped = content_left_after_collapse(false);
+        assert!(jumped.iter().all(|&left| left < 20.0));
+    }
+}
@@ -0,0 +1,94 @@
+//! Icons of the panel chrome: the collapse button and the resize handle.
+
+use crate::Side;
+use egui::{emath::Rot2, ImageSource, Painter, Rect, Ui, Vec2};
+use std::sync::Arc;
+
+/// What an icon is drawn for.
+#[derive(Clone, Copy)]
+pub struct IconState {
+    /// Whether the panel is expanded.
+    pub expanded: bool,
+    /// Side of the panel's current config.
+    pub side: Side,
+    /// Whether the pointer is on the icon.
+    pub hovered: bool,
+}
+
+type PaintIconFn = dyn Fn(&Painter, Rect, IconState) + Send + Sync;
+
+/// Custom icon for the collapse button ([`crate::DynamicPanel::collapse_button`]) or the resize handle
+/// ([`crate::DynamicPanel::handle_icon`]).
+#[derive(Clone)]
+pub enum PanelIcon {
+    /// Images drawn for a panel on the left and rotated to match the side of the panel's current config.
+    Image {
+        collapsed: ImageSource<'static>,
+        expanded: ImageSource<'static>,
+    },
+    /// Closure painting the icon into the given rect. It is not rotated, use [`IconState::side`] for that.
+    Paint(Arc<PaintIconFn>),
+}
+
+impl PanelIcon {
+    /// The same image for both states.
+    pub fn image(source: impl Into<ImageSource<'static>>) -> Self {
+        let source = source.into();
+        Self::Image {
+            collapsed: source.clone(),
+            expanded: source,
+        }
+    }
+
+    /// Separate images for the collapsed and expanded state.
+    pub fn images(
+        collapsed: impl Into<ImageSource<'static>>,
+        expanded: impl Into<ImageSource<'static>>,
+    ) -> Self {
+        Self::Image {
+            collapsed: collapsed.into(),
+            expanded: expanded.into(),
+        }
+    }
+
+    pub fn paint(f: impl Fn(&Painter, Rect, IconState) + Send + Sync + 'static) -> Self {
+        Self::Paint(Arc::new(f))
+    }
+
+    pub(crate) fn draw(&self, ui: &Ui, rect: Rect, state: IconState) {
+        match self {
+            PanelIcon::Image {
+                collapsed,
+                expanded,
+            } => {
+                let source = if state.expanded { expanded } else { collapsed };
+                egui::Image::new(source.clone())
+                    .rotate(state.side.icon_angle(), Vec2::splat(0.5))
+                    .paint_at(ui, rect);
+            }
+            PanelIcon::Paint(f) => f(ui.painter(), rect, state),
+        }
+    }
+}
+
+/// Built-in collapse icon: a chevron pointing towards the side the panel collapses to.
+pub(crate) fn paint_chevron(ui: &Ui, rect: Rect, state: IconState) {
+    let visuals = if state.hovered {
+        &ui.visuals().widgets.hovered
+    } else {
+        &ui.visuals().widgets.inactive
+    };
+    let half = rect.size().min_elem() / 4.0;
+    // Drawn for a left panel: `<` collapses it, `>` expands it.
+    let tip = if state.expanded { -half } else { half };
+    let rot = Rot2::from_angle(state.side.icon_angle());
+    let c = rect.center();
+    let points = [
+        Vec2::new(-tip / 2.0, -half),
+        Vec2::new(tip / 2.0, 0.0),
+        Vec2::new(-tip / 2.0, half),
+    ]
+    .map(|v| c + rot * v);
+    ui.painter()
+        .add(egui::Shape::line(points.to_vec(), visuals.fg_stroke));
+}
@@ -0,0 +1,148 @@
+//! A [`DynamicPanel`] whose configs are selected by a key of the app's own type instead of an index.
+
+use crate::{DynamicPanel, PanelCfg};
+use egui::{Context, Id};
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+
+/// A [`DynamicPanel`] with one config per key, e.g. per variant of a `LayoutKind` enum. The configs come from a
+/// function of the key, so an exhaustive `match` makes sure every key has a config. Which keys get one is still a
+/// list passed at runtime, though: a key the choice function returns without being in that list is only noticed
+/// when it is chosen, and handled like an unknown key of [`DynamicPanel::with_named_choice_function`] (the
+/// [fallback index](DynamicPanel::with_fallback_index), or a panic in [strict mode](DynamicPanel::strict)).
+///
+/// ```ignore
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// enum LayoutKind { Phone, Desktop }
+///
+/// let sidebar = KeyedPanel::new(
+///     DynamicPanel::new("sidebar"),
+///     [LayoutKind::Phone, LayoutKind::Desktop],
+///     |kind| match kind {
+///         LayoutKind::Phone => SinglePanelCfg::bottom().into(),
+///         LayoutKind::Desktop => SinglePanelCfg::left().into(),
+///     },
+/// )
+/// .with_choice_function(|ctx| {
+///     if ctx.screen_rect().width() < 500.0 { LayoutKind::Phone } else { LayoutKind::Desktop }
+/// });
+/// sidebar.show_dynamic(ctx, |ui| ui.label("…"));
+/// ```
+///
+/// All methods of [`DynamicPanel`] are available through `Deref`, the builders have to be applied to the panel
+/// passed to [`Self::new`].
+pub struct KeyedPanel<'a, K> {
+    panel: DynamicPanel<'a>,
+    keys: Vec<K>,
+}
+
+impl<'a, K: Copy + Eq + Hash + 'static> KeyedPanel<'a, K> {
+    /// Replaces the configs of `panel` with one config per key, created by `cfg`.
+    pub fn new(
+        panel: DynamicPanel<'a>,
+        keys: impl IntoIterator<Item = K>,
+        cfg: impl Fn(K) -> PanelCfg,
+    ) -> Self {
+        let mut panel = panel.with_panels(std::iter::empty());
+        let keys: Vec<K> = keys.into_iter().collect();
+        for &key in &keys {
+            let index = panel.push_panel(cfg(key));
+            panel.set_key(key_id(key), index);
+        }
+        Self { panel, keys }
+    }
+
+    /// Choice function returning the key of the config to show, like [`DynamicPanel::with_named_choice_function`].
+    pub fn with_choice_function<F: Fn(&'a Context) -> K + 'static>(
+        mut self,
+        choice_function: F,
+    ) -> Self {
+        self.panel.key_choice_f = Some(Box::new(move |ctx| key_id(choice_function(ctx))));
+        self
+    }
+
+    /// Index of the config of `key`.
+    pub fn index_of(&self, key: K) -> Option<usize> {
+        self.panel.key_index(key_id(key))
+    }
+
+    /// Key of the config shown last by a `show_dynamic*` method.
+    pub fn current_key(&self, ctx: &Context) -> Option<K> {
+        let index = self.panel.current_index(ctx)?;
+        self.keys
+            .iter()
+            .copied()
+            .find(|&key| self.index_of(key) == Some(index))
+    }
+
+    /// The underlying panel, e.g. for adding it to a [`crate::PanelGroup`].
+    pub fn panel(&self) -> &DynamicPanel<'a> {
+        &self.panel
+    }
+}
+
+fn key_id(key: impl Hash) -> Id {
+    Id::new(("egui_dpanel_key", key))
+}
+
+impl<'a, K> Deref for KeyedPanel<'a, K> {
+    type Target = DynamicPanel<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.panel
+    }
+}
+
+impl<K> DerefMut for KeyedPanel<'_, K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.panel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LayoutProbe;
+    use crate::SinglePanelCfg;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum Layout {
+        Phone,
+        Desktop,
+        Watch,
+    }
+
+    fn sidebar<'a>(chosen: Layout) -> KeyedPanel<'a, Layout> {
+        let panel = DynamicPanel::new("sidebar")
+            .with_fallback_index(0)
+            .strict(false);
+        KeyedPanel::new(
+            panel,
+            [Layout::Phone, Layout::Desktop],
+            |layout| match layout {
+                Layout::Phone => SinglePanelCfg::bottom().into(),
+                Layout::Desktop | Layout::Watch => SinglePanelCfg::left().into(),
+            },
+        )
+        .with_choice_function(move |_| chosen)
+    }
+
+    fn shown(chosen: Layout) -> Option<Layout> {
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            sidebar(chosen).show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        sidebar(chosen).current_key(probe.context())
+    }
+
+    #[test]
+    fn keys_select_their_config() {
+        assert_eq!(shown(Layout::Desktop), Some(Layout::Desktop));
+        assert_eq!(shown(Layout::Phone), Some(Layout::Phone));
+    }
+
+    #[test]
+    fn unlisted_keys_fall_back() {
+        assert_eq!(shown(Layout::Watch), Some(Layout::Phone));
+    }
+}
@@ -1,33 +1,421 @@
-use egui::{Context, Frame, InnerResponse, SidePanel, TopBottomPanel, Ui};
+mod accordion;
+pub mod choice;
+#[cfg(debug_assertions)]
+mod collision;
+mod commands;
+#[cfg(feature = "demo")]
+pub mod demo;
+mod focus;
+mod group;
+mod icon;
+mod keyed;
+mod macros;
+mod observer;
+#[cfg(feature = "record")]
+pub mod record;
+mod slots;
+mod stack;
+mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod toggle_bar;
+mod typed;
+#[cfg(feature = "url_hash")]
+pub mod url_hash;
+
+pub use accordion::{Accordion, AccordionUi};
+pub use commands::{PanelCommand, PanelCommands};
+use egui::containers::panel::PanelState;
+use egui::{
+    Context, EventFilter, Frame, Id, InnerResponse, Key, LayerId, Margin, Modifiers, Rangef, Rect,
+    Sense, SidePanel, Style, TopBottomPanel, Ui, UiBuilder,
+};
+pub use group::PanelGroup;
+pub use icon::{IconState, PanelIcon};
+pub use keyed::KeyedPanel;
+pub use observer::LayoutObserver;
+pub use slots::PanelSlots;
+use smallvec::{smallvec, SmallVec};
+pub use stack::{stack_region, StackRegion, StackedPanelCfg};
+pub use state::DynamicPanelState;
+#[cfg(feature = "animation")]
+use state::Transition;
+use std::borrow::Cow;
+use std::sync::Arc;
+pub use toggle_bar::PanelToggleBar;
+pub use typed::{SideCfg, TopBottomCfg};
+
+/// Separator grab radius used by [`SinglePanelCfg::touch_friendly`], roughly half a fingertip.
+const TOUCH_GRAB_RADIUS: f32 = 12.0;
+
+/// Smallest side length of the [collapse button](DynamicPanel::collapse_button) in touch mode, a fingertip.
+const TOUCH_BUTTON_SIZE: f32 = 24.0;
+
+/// Smallest jump of a panel within one frame that makes the content of a [`PanelGroup`] member slide.
+#[cfg(feature = "animation")]
+const SLIDE_MIN_JUMP: f32 = 16.0;
+
+/// How far past its limits a panel can be pulled with [`DynamicPanel::rubber_band`], reached only asymptotically.
+const RUBBER_BAND_DISTANCE: f32 = 60.0;
+
+/// Distance of a [`Fab`] from the screen edges.
+const FAB_MARGIN: f32 = 16.0;
+
+/// Depth of the band along the screen edge a panel is docked at, where a detached panel is docked again.
+const DOCK_ZONE: f32 = 48.0;
+
+fn reduce_motion_id() -> Id {
+    Id::new("egui_dpanel_reduce_motion")
+}
+
+/// Sets the app-wide "reduce motion" preference honored by every [`DynamicPanel`] without its own
+/// [`DynamicPanel::reduce_motion`] setting. egui doesn't expose the OS preference, so integrations that can read it
+/// (e.g. `prefers-reduced-motion` on the web) should forward it here.
+pub fn set_reduce_motion(ctx: &Context, reduce: bool) {
+    ctx.data_mut(|d| d.insert_temp(reduce_motion_id(), reduce));
+}
+
+/// The app-wide "reduce motion" preference, see [`set_reduce_motion`].
+pub fn reduce_motion(ctx: &Context) -> bool {
+    ctx.data(|d| d.get_temp(reduce_motion_id()))
+        .unwrap_or(false)
+}
+
+fn safe_area_id() -> Id {
+    Id::new("egui_dpanel_safe_area")
+}
+
+/// Sets the safe-area insets of the screen (notch, home indicator, rounded corners), as reported by the
+/// integration, used by configs with [`SafeArea::Integration`].
+pub fn set_safe_area_insets(ctx: &Context, insets: Margin) {
+    ctx.data_mut(|d| d.insert_temp(safe_area_id(), insets));
+}
+
+/// The safe-area insets set with [`set_safe_area_insets`], zero if there are none.
+pub fn safe_area_insets(ctx: &Context) -> Margin {
+    ctx.data(|d| d.get_temp(safe_area_id())).unwrap_or_default()
+}
+
+fn screen_preview_id() -> Id {
+    Id::new("egui_dpanel_screen_preview")
+}
+
+/// A simulated screen size for previewing the layouts of other devices without resizing the window, see
+/// [`set_screen_preview`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenPreview {
+    pub size: egui::Vec2,
+    /// Outline the simulated screen in the top left corner of the window.
+    pub show_boundary: bool,
+}
+
+/// Developer mode: makes the panels choose their configs as if the screen had the size of `preview`, e.g. to
+/// check the phone layout inside a desktop window. The panels still fill the real screen. `None` turns it off.
+///
+/// Only choice functions reading [`screen_rect`] or [`breakpoint_rect`] (like the presets, [`choice`] and
+/// [`dpanel!`]) see the preview, rect choice functions see the available space clipped to it.
+pub fn set_screen_preview(ctx: &Context, preview: Option<ScreenPreview>) {
+    ctx.data_mut(|d| match preview {
+        Some(preview) => d.insert_temp(screen_preview_id(), preview),
+        None => d.remove::<ScreenPreview>(screen_preview_id()),
+    });
+}
+
+/// The preview set with [`set_screen_preview`], if there is one.
+pub fn screen_preview(ctx: &Context) -> Option<ScreenPreview> {
+    ctx.data(|d| d.get_temp(screen_preview_id()))
+}
+
+fn initial_screen_size_id() -> Id {
+    Id::new("egui_dpanel_initial_screen_size")
+}
+
+/// The size of the window the integration opens, e.g. the inner size of eframe's `NativeOptions::viewport`.
+/// Integrations that report the screen size only after the first frame leave egui with a placeholder until then;
+/// with the initial size the panels choose their configs for the real window right away. Without it, the
+/// `show_dynamic*` methods show nothing until the size is known, rather than flashing the wrong layout.
+pub fn set_initial_screen_size(ctx: &Context, size: egui::Vec2) {
+    ctx.data_mut(|d| d.insert_temp(initial_screen_size_id(), size));
+}
+
+/// The size set with [`set_initial_screen_size`], if there is one.
+pub fn initial_screen_size(ctx: &Context) -> Option<egui::Vec2> {
+    ctx.data(|d| d.get_temp(initial_screen_size_id()))
+}
+
+/// Whether egui knows the size of the screen, rather than an empty rect or the 10 000 × 10 000 points it assumes
+/// before the integration reported one.
+fn screen_known(ctx: &Context) -> bool {
+    let screen = ctx.screen_rect();
+    screen.is_positive() && screen.size() != egui::Vec2::splat(10_000.0)
+}
+
+/// The screen choice functions should look at: the [preview](set_screen_preview) in the top left corner of the
+/// window while there is one, `ctx.screen_rect()` otherwise. Before egui knows the size of the screen, the
+/// [initial size](set_initial_screen_size) if there is one.
+pub fn screen_rect(ctx: &Context) -> Rect {
+    let mut screen = ctx.screen_rect();
+    if let Some(size) = initial_screen_size(ctx).filter(|_| !screen_known(ctx)) {
+        screen = Rect::from_min_size(egui::Pos2::ZERO, size);
+    }
+    match screen_preview(ctx) {
+        Some(preview) => Rect::from_min_size(screen.min, preview.size),
+        None => screen,
+    }
+}
+
+fn zoom_aware_id() -> Id {
+    Id::new("egui_dpanel_zoom_aware_breakpoints")
+}
+
+/// Makes breakpoints compare against the size of the window before egui's zoom is applied, so zooming in doesn't
+/// switch to the phone layout. Applies to everything using [`breakpoint_rect`]: the presets, [`choice`] and
+/// [`dpanel!`]. Off by default, breakpoints are in zoomed points like the rest of egui.
+pub fn set_zoom_aware_breakpoints(ctx: &Context, zoom_aware: bool) {
+    ctx.data_mut(|d| d.insert_temp(zoom_aware_id(), zoom_aware));
+}
+
+/// Whether breakpoints ignore the zoom, see [`set_zoom_aware_breakpoints`].
+pub fn zoom_aware_breakpoints(ctx: &Context) -> bool {
+    ctx.data(|d| d.get_temp(zoom_aware_id())).unwrap_or(false)
+}
+
+/// The [`screen_rect`] breakpoints should be compared against: scaled back to unzoomed points if
+/// [`set_zoom_aware_breakpoints`] is on.
+pub fn breakpoint_rect(ctx: &Context) -> Rect {
+    let screen = screen_rect(ctx);
+    if zoom_aware_breakpoints(ctx) {
+        Rect::from_min_size(screen.min, screen.size() * ctx.zoom_factor())
+    } else {
+        screen
+    }
+}
+
+fn panel_defaults_id() -> Id {
+    Id::new("egui_dpanel_defaults")
+}
+
+/// Settings every [`DynamicPanel`] and its docked configs inherit unless they set them themselves, registered once
+/// with [`set_panel_defaults`] instead of being repeated for each panel.
+#[derive(Clone, Default)]
+pub struct PanelDefaults {
+    pub frame: Option<Frame>,
+    pub resizable: Option<bool>,
+    pub show_separator_line: Option<bool>,
+    /// See [`SinglePanelCfg::resize_grab_radius`](SinglePanelCfg#structfield.resize_grab_radius).
+    pub resize_grab_radius: Option<f32>,
+    /// See [`SinglePanelCfg::keyboard_resize_step`](SinglePanelCfg#structfield.keyboard_resize_step).
+    pub keyboard_resize_step: Option<f32>,
+    /// See [`DynamicPanel::animated`].
+    pub animated: Option<bool>,
+}
+
+/// Sets the defaults inherited by all panels shown with `ctx`, see [`PanelDefaults`].
+pub fn set_panel_defaults(ctx: &Context, defaults: PanelDefaults) {
+    ctx.data_mut(|d| d.insert_temp(panel_defaults_id(), Arc::new(defaults)));
+}
+
+/// The defaults set with [`set_panel_defaults`], all unset if there are none.
+pub fn panel_defaults(ctx: &Context) -> Arc<PanelDefaults> {
+    ctx.data(|d| d.get_temp(panel_defaults_id()))
+        .unwrap_or_default()
+}
 
 /// Configutation for a Panel
+#[allow(clippy::large_enum_variant)] // configs are built once and then only borrowed
 pub enum PanelCfg {
     Single(SinglePanelCfg),
     Collapsible(CollapsiblePanelCfg),
+    Overlay(OverlayPanelCfg),
+    Stacked(StackedPanelCfg),
 }
 
 impl PanelCfg {
+    /// The docked config used in collapsed state.
+    ///
+    /// # Panics
+    /// For overlays, which aren't docked. See [`Self::docked_collapsed`].
     pub fn collapsed(&self) -> &SinglePanelCfg {
+        self.docked_collapsed()
+            .expect("overlays have no docked config")
+    }
+
+    /// The docked config used in expanded state.
+    ///
+    /// # Panics
+    /// For overlays, which aren't docked. See [`Self::docked_expanded`].
+    pub fn expanded(&self) -> &SinglePanelCfg {
+        self.docked_expanded()
+            .expect("overlays have no docked config")
+    }
+
+    /// The docked config used in collapsed state, `None` for configs that aren't docked panels.
+    pub fn docked_collapsed(&self) -> Option<&SinglePanelCfg> {
         match self {
-            PanelCfg::Single(s) => s,
-            PanelCfg::Collapsible(c) => &c.collapsed,
+            PanelCfg::Single(s) => Some(s),
+            PanelCfg::Collapsible(c) => Some(&c.collapsed),
+            PanelCfg::Overlay(_) => None,
+            PanelCfg::Stacked(s) => Some(&s.panel),
         }
     }
 
-    pub fn expanded(&self) -> &SinglePanelCfg {
+    /// The docked config used in expanded state, `None` for configs that aren't docked panels.
+    pub fn docked_expanded(&self) -> Option<&SinglePanelCfg> {
         match self {
-            PanelCfg::Single(s) => s,
-            PanelCfg::Collapsible(c) => &c.expanded,
+            PanelCfg::Single(s) => Some(s),
+            PanelCfg::Collapsible(c) => Some(&c.expanded),
+            PanelCfg::Overlay(_) => None,
+            PanelCfg::Stacked(s) => Some(&s.panel),
+        }
+    }
+}
+
+/// Content floating in an `egui::Area` above everything else instead of reserving space like a docked panel,
+/// e.g. a floating toolbar. It is pinned to a corner or edge of the screen (or the parent Ui when shown inside one).
+pub struct OverlayPanelCfg {
+    /// Corner or edge the overlay is pinned to, the overlay's own corner/edge is placed there.
+    pub anchor: egui::Align2,
+    /// Offset from the anchor, e.g. `vec2(-8.0, 8.0)` to keep a `RIGHT_TOP` overlay away from the screen edges.
+    pub offset: egui::Vec2,
+    pub interactable: Option<bool>,
+    pub frame: Option<Frame>,
+    /// Fade out this many seconds after the overlay was switched to or [revealed](DynamicPanel::reveal),
+    /// unless the pointer is on it.
+    pub auto_hide_after: Option<f32>,
+    /// Title bar with a close button above the content. Closing collapses the panel.
+    pub title: Option<String>,
+    /// Color the rest of the screen is dimmed with while the overlay is shown. The dimmed area blocks input and
+    /// collapses the panel when clicked.
+    pub scrim: Option<egui::Color32>,
+    /// Floating action button shown in place of the collapsed overlay, opening it when clicked.
+    pub fab: Option<Fab>,
+}
+
+impl OverlayPanelCfg {
+    pub fn new(anchor: egui::Align2, offset: egui::Vec2) -> Self {
+        Self {
+            anchor,
+            offset,
+            interactable: None,
+            frame: None,
+            auto_hide_after: None,
+            title: None,
+            scrim: None,
+            fab: None,
+        }
+    }
+
+    /// Show a title bar with a close button, see [`Self::title`].
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Dim and block the rest of the screen, see [`Self::scrim`].
+    pub fn scrim(mut self, color: egui::Color32) -> Self {
+        self.scrim = Some(color);
+        self
+    }
+
+    /// Stand in for the overlay with a floating action button labelled `icon` (e.g. an emoji) in the `corner` of
+    /// the screen while the panel is collapsed, see [`DynamicPanel::show_dynamic_collapsible`].
+    pub fn fab(mut self, icon: impl Into<String>, corner: egui::Align2) -> Self {
+        self.fab = Some(Fab::new(icon, corner));
+        self
+    }
+
+    /// Closed by the title bar, the scrim or Escape rather than only by the app.
+    fn is_dismissable(&self) -> bool {
+        self.title.is_some() || self.scrim.is_some()
+    }
+
+    /// Hide the overlay again `seconds` after it was shown, see [`Self::auto_hide_after`].
+    pub fn auto_hide_after(mut self, seconds: f32) -> Self {
+        self.auto_hide_after = Some(seconds);
+        self
+    }
+
+    fn apply_area(&self, area: egui::Area) -> egui::Area {
+        if let Some(b) = self.interactable {
+            area.interactable(b)
+        } else {
+            area
+        }
+    }
+}
+
+/// Round button floating above everything else in a corner of the screen, see [`OverlayPanelCfg::fab`].
+#[derive(Clone)]
+pub struct Fab {
+    /// Text on the button, e.g. an emoji.
+    pub icon: String,
+    /// Corner or edge of the screen the button is pinned to.
+    pub anchor: egui::Align2,
+    /// Offset from the anchor, by default keeping the button 16 points away from the screen edges.
+    pub offset: egui::Vec2,
+    /// Diameter of the button.
+    pub size: f32,
+}
+
+impl Fab {
+    pub fn new(icon: impl Into<String>, anchor: egui::Align2) -> Self {
+        Self {
+            icon: icon.into(),
+            anchor,
+            offset: -FAB_MARGIN * anchor.to_sign(),
+            size: 56.0,
+        }
+    }
+}
+
+/// Image drawn behind the content of a docked config, inside its frame, see [`SinglePanelCfg::background`].
+/// Needs an image loader for the source, e.g. `egui_extras::install_image_loaders`.
+#[derive(Clone, Debug)]
+pub struct PanelBackground {
+    pub image: egui::ImageSource<'static>,
+    pub fit: BackgroundFit,
+    /// Multiplied with the image, e.g. a translucent white for a subtle texture.
+    pub tint: egui::Color32,
+}
+
+impl PanelBackground {
+    pub fn new(image: impl Into<egui::ImageSource<'static>>, fit: BackgroundFit) -> Self {
+        Self {
+            image: image.into(),
+            fit,
+            tint: egui::Color32::WHITE,
         }
     }
 }
 
+/// How a [`PanelBackground`] covers the panel.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BackgroundFit {
+    /// Stretched to the panel's size.
+    #[default]
+    Stretch,
+    /// Repeated from the panel's top left corner, each tile this many points large.
+    Tile(egui::Vec2),
+}
+
+impl From<OverlayPanelCfg> for PanelCfg {
+    fn from(cfg: OverlayPanelCfg) -> Self {
+        PanelCfg::Overlay(cfg)
+    }
+}
+
 /// Holds two configurations, for collapsed and expanded state respectively.
 pub struct CollapsiblePanelCfg {
     pub collapsed: SinglePanelCfg,
     pub expanded: SinglePanelCfg,
 }
 
+impl Default for CollapsiblePanelCfg {
+    fn default() -> Self {
+        Self::new(SinglePanelCfg::default(), SinglePanelCfg::default())
+    }
+}
+
 impl CollapsiblePanelCfg {
     pub fn new(collapsed: SinglePanelCfg, expanded: SinglePanelCfg) -> Self {
         Self {
@@ -38,8 +426,11 @@ impl CollapsiblePanelCfg {
 }
 
 /// Holds all possible configurable parameters for SidePanel/TopBottomPanel and the Side (Left, Right, Top, Bottom)
+///
+/// Fields of the other axis are ignored, [`SideCfg`] and [`TopBottomCfg`] build configs without them.
+#[derive(Clone)]
 pub struct SinglePanelCfg {
-    side: Side,
+    pub side: Side,
     pub resizable: Option<bool>,
     pub show_separator_line: Option<bool>,
     pub default_width: Option<f32>,
@@ -53,11 +444,38 @@ pub struct SinglePanelCfg {
     pub height_range: Option<(f32, f32)>,
     pub exact_height: Option<f32>,
     pub frame: Option<Frame>,
+    /// Half-width of the separator's interaction area. Larger values make the panel easier to resize
+    /// with a finger, without changing how thick the separator line is drawn.
+    pub resize_grab_radius: Option<f32>,
+    /// Makes the separator focusable; while it has focus the arrow keys grow or shrink the panel by this many points.
+    pub keyboard_resize_step: Option<f32>,
+    /// Padding that keeps the content out of the screen's unsafe areas.
+    pub safe_area: SafeArea,
+    /// Image behind the content. Configs of a panel have their own, e.g. a texture on the expanded sidebar and a
+    /// flat fill on the collapsed rail.
+    pub background: Option<PanelBackground>,
+    /// Size in the vocabulary of `egui_extras` tables and strips, resolved against the space the panel is shown in.
+    /// Replaces the width/height fields of the panel's axis.
+    /// `exact` and `initial` sizes behave like their `f32` counterparts, `relative` sizes are a fraction of the
+    /// screen (or the parent Ui), and `remainder` takes all of it. The range of the size is respected in any case.
+    #[cfg(feature = "egui_extras")]
+    pub size: Option<egui_extras::Size>,
+    /// Applied to the `SidePanel` after all other fields, for egui options this crate doesn't wrap.
+    pub map_side_panel: Option<Arc<dyn Fn(SidePanel) -> SidePanel + Send + Sync>>,
+    /// Applied to the `TopBottomPanel` after all other fields, for egui options this crate doesn't wrap.
+    pub map_top_bottom: Option<Arc<dyn Fn(TopBottomPanel) -> TopBottomPanel + Send + Sync>>,
+}
+
+impl Default for SinglePanelCfg {
+    /// A left panel with egui's defaults.
+    fn default() -> Self {
+        Self::left()
+    }
 }
 
-impl Into<PanelCfg> for SinglePanelCfg {
-    fn into(self) -> PanelCfg {
-        PanelCfg::Single(self)
+impl From<SinglePanelCfg> for PanelCfg {
+    fn from(cfg: SinglePanelCfg) -> Self {
+        PanelCfg::Single(cfg)
     }
 }
 
@@ -94,6 +512,14 @@ impl SinglePanelCfg {
             height_range: None,
             exact_height: None,
             frame: None,
+            resize_grab_radius: None,
+            keyboard_resize_step: None,
+            safe_area: SafeArea::Ignore,
+            background: None,
+            #[cfg(feature = "egui_extras")]
+            size: None,
+            map_side_panel: None,
+            map_top_bottom: None,
         }
     }
 
@@ -101,451 +527,5082 @@ impl SinglePanelCfg {
         self.side
     }
 
-    pub fn apply_top_bottom(&self, panel: TopBottomPanel) -> TopBottomPanel {
-        let panel = if let Some(b) = self.resizable {
-            panel.resizable(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.show_separator_line {
-            panel.show_separator_line(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.default_height {
-            panel.default_height(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.min_height {
-            panel.min_height(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.max_height {
-            panel.max_height(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.height_range {
-            panel.height_range(b.0..=b.1)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.exact_height {
-            panel.exact_height(b)
-        } else {
-            panel
-        };
-        if let Some(f) = self.frame {
-            panel.frame(f)
+    /// Customize the underlying `SidePanel` after all other fields were applied.
+    pub fn map_side_panel(
+        mut self,
+        f: impl Fn(SidePanel) -> SidePanel + Send + Sync + 'static,
+    ) -> Self {
+        self.map_side_panel = Some(Arc::new(f));
+        self
+    }
+
+    /// Customize the underlying `TopBottomPanel` after all other fields were applied.
+    pub fn map_top_bottom(
+        mut self,
+        f: impl Fn(TopBottomPanel) -> TopBottomPanel + Send + Sync + 'static,
+    ) -> Self {
+        self.map_top_bottom = Some(Arc::new(f));
+        self
+    }
+
+    /// Size the panel with an `egui_extras::Size`, see [`Self::size`](#structfield.size).
+    #[cfg(feature = "egui_extras")]
+    pub fn size(mut self, size: egui_extras::Size) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets `default_width` for left/right and `default_height` for top/bottom configs.
+    pub fn default_size(mut self, size: f32) -> Self {
+        if self.side.is_lr() {
+            self.default_width = Some(size);
         } else {
-            panel
+            self.default_height = Some(size);
         }
+        self
     }
 
-    pub fn apply_side(&self, panel: SidePanel) -> SidePanel {
-        let panel = if let Some(b) = self.resizable {
-            panel.resizable(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.show_separator_line {
-            panel.show_separator_line(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.default_width {
-            panel.default_width(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.min_width {
-            panel.min_width(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.max_width {
-            panel.max_width(b)
-        } else {
-            panel
-        };
-        let panel = if let Some(b) = self.width_range {
-            panel.width_range(b.0..=b.1)
+    /// Sets `min_width` for left/right and `min_height` for top/bottom configs.
+    pub fn min_size(mut self, size: f32) -> Self {
+        if self.side.is_lr() {
+            self.min_width = Some(size);
         } else {
-            panel
-        };
-        let panel = if let Some(b) = self.exact_width {
-            panel.exact_width(b)
-        } else {
-            panel
-        };
-        if let Some(f) = self.frame {
-            panel.frame(f)
-        } else {
-            panel
+            self.min_height = Some(size);
         }
+        self
     }
-}
 
-/// Side of a Panel (Left, Right : Side Panel), (Top, Bottom: TopBottomPanel)
-#[derive(Copy, Clone, Eq, PartialEq)]
-pub enum Side {
-    Left,
-    Right,
-    Top,
-    Bottom,
-}
+    /// Sets `max_width` for left/right and `max_height` for top/bottom configs.
+    pub fn max_size(mut self, size: f32) -> Self {
+        if self.side.is_lr() {
+            self.max_width = Some(size);
+        } else {
+            self.max_height = Some(size);
+        }
+        self
+    }
 
-impl Side {
-    pub fn is_lr(&self) -> bool {
-        match self {
-            Side::Left | Side::Right => true,
-            _ => false,
+    /// Sets `width_range` for left/right and `height_range` for top/bottom configs.
+    pub fn size_range(mut self, min: f32, max: f32) -> Self {
+        if self.side.is_lr() {
+            self.width_range = Some((min, max));
+        } else {
+            self.height_range = Some((min, max));
         }
+        self
     }
-}
 
-/// Panel that can be displayed dynamically as a `SidePanel` or `TopBottomPanel` - e.g. if the screen size is too small.
-pub struct DynamicPanel<'a> {
-    name: String,
-    panels: Vec<PanelCfg>,
-    choice_f: Option<Box<dyn Fn(&'a egui::Context) -> usize>>,
-}
+    /// Sets `exact_width` for left/right and `exact_height` for top/bottom configs.
+    pub fn exact_size(mut self, size: f32) -> Self {
+        if self.side.is_lr() {
+            self.exact_width = Some(size);
+        } else {
+            self.exact_height = Some(size);
+        }
+        self
+    }
 
-impl<'a> DynamicPanel<'a> {
-    /// Constructor. Name will be used for the Panel Id.
-    pub fn new(name: &str) -> Self {
-        Self {
-            name: name.to_string(),
-            panels: vec![],
-            choice_f: None,
+    /// This config on the opposite side (left ↔ right, top ↔ bottom), with the margins, rounding and shadow of its
+    /// frame mirrored along the same axis.
+    pub fn mirrored(&self) -> Self {
+        let mut cfg = self.clone();
+        cfg.side = self.side.opposite();
+        if let Some(frame) = &mut cfg.frame {
+            let lr = self.side.is_lr();
+            for margin in [&mut frame.inner_margin, &mut frame.outer_margin] {
+                if lr {
+                    std::mem::swap(&mut margin.left, &mut margin.right);
+                } else {
+                    std::mem::swap(&mut margin.top, &mut margin.bottom);
+                }
+            }
+            let r = &mut frame.rounding;
+            if lr {
+                std::mem::swap(&mut r.nw, &mut r.ne);
+                std::mem::swap(&mut r.sw, &mut r.se);
+            } else {
+                std::mem::swap(&mut r.nw, &mut r.sw);
+                std::mem::swap(&mut r.ne, &mut r.se);
+            }
+            let offset = &mut frame.shadow.offset;
+            if lr {
+                offset.x = -offset.x;
+            } else {
+                offset.y = -offset.y;
+            }
         }
+        cfg
     }
 
-    /// Show the Panel dynamically, based on the choice function.
-    pub fn show_dynamic<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ctx: &'a egui::Context,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        self.choice_f
-            .as_ref()
-            .and_then(|f| self.show(ctx, (f)(ctx), content))
+    /// This config at the top, see [`Self::to_side`].
+    pub fn to_top(&self) -> Self {
+        self.to_side(Side::Top)
     }
 
-    /// Show the Panel dynamically inside a Ui, based on the choice function.
-    pub fn show_dynamic_inside<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ctx: &'a Context,
-        ui: &mut Ui,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        self.choice_f
-            .as_ref()
-            .and_then(|f| self.show_inside(ui, f(ctx), content))
+    /// This config at the bottom, see [`Self::to_side`].
+    pub fn to_bottom(&self) -> Self {
+        self.to_side(Side::Bottom)
     }
 
-    /// Show the Panel dynamically and animated, based on the choice function.
-    pub fn show_dynamic_animated<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ctx: &'a Context,
-        is_expanded: bool,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        self.choice_f
-            .as_ref()
-            .and_then(|f| self.show_animated(ctx, f(ctx), is_expanded, content))
+    /// This config on the left, see [`Self::to_side`].
+    pub fn to_left(&self) -> Self {
+        self.to_side(Side::Left)
     }
 
-    /// Show the Panel dynamically and animated inside a Ui, based on the choice function.
-    pub fn show_dynamic_animated_inside<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ctx: &'a Context,
-        ui: &mut Ui,
-        is_expanded: bool,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        self.choice_f
-            .as_ref()
-            .and_then(|f| self.show_animated_inside(ui, f(ctx), is_expanded, content))
+    /// This config on the right, see [`Self::to_side`].
+    pub fn to_right(&self) -> Self {
+        self.to_side(Side::Right)
     }
 
-    /// Show the Panel with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
-    pub fn show<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ctx: &'a Context,
-        index: usize,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        if let Some(cfg) = self.panels.get(index) {
-            Some(Self::show_panel(
-                cfg.expanded(),
-                ctx,
-                content,
-                self.name.clone(),
-            ))
-        } else {
-            None
+    /// This config moved to `side`. When the axis changes, the width fields become the height fields and vice
+    /// versa, e.g. a 200 points wide sidebar becomes a 200 points high bottom bar.
+    pub fn to_side(&self, side: Side) -> Self {
+        let mut cfg = self.clone();
+        cfg.side = side;
+        if side.is_lr() != self.side.is_lr() {
+            std::mem::swap(&mut cfg.default_width, &mut cfg.default_height);
+            std::mem::swap(&mut cfg.min_width, &mut cfg.min_height);
+            std::mem::swap(&mut cfg.max_width, &mut cfg.max_height);
+            std::mem::swap(&mut cfg.width_range, &mut cfg.height_range);
+            std::mem::swap(&mut cfg.exact_width, &mut cfg.exact_height);
         }
+        cfg
     }
 
-    /// Show the Panel inside a Ui with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
-    pub fn show_inside<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ui: &mut Ui,
-        index: usize,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        if let Some(cfg) = self.panels.get(index) {
-            Some(Self::show_panel_inside(
-                cfg.expanded(),
-                ui,
-                content,
-                self.name.clone(),
-            ))
-        } else {
-            None
+    /// This config with all sizes multiplied by `factor`: the width and height fields, the frame's margins and
+    /// rounding, the separator's grab radius and the keyboard resize step. Relative `egui_extras` sizes only have
+    /// their range scaled.
+    pub fn scaled(&self, factor: f32) -> Self {
+        let scale = |size: &mut Option<f32>| *size = size.map(|size| size * factor);
+        let scale_range =
+            |range: &mut Option<(f32, f32)>| *range = range.map(|(a, b)| (a * factor, b * factor));
+        let mut cfg = self.clone();
+        for size in [
+            &mut cfg.default_width,
+            &mut cfg.min_width,
+            &mut cfg.max_width,
+            &mut cfg.exact_width,
+            &mut cfg.default_height,
+            &mut cfg.min_height,
+            &mut cfg.max_height,
+            &mut cfg.exact_height,
+            &mut cfg.resize_grab_radius,
+            &mut cfg.keyboard_resize_step,
+        ] {
+            scale(size);
+        }
+        scale_range(&mut cfg.width_range);
+        scale_range(&mut cfg.height_range);
+        if let Some(frame) = &mut cfg.frame {
+            frame.inner_margin *= factor;
+            frame.outer_margin *= factor;
+            frame.rounding *= factor;
         }
+        if let Some(PanelBackground {
+            fit: BackgroundFit::Tile(tile),
+            ..
+        }) = &mut cfg.background
+        {
+            *tile *= factor;
+        }
+        #[cfg(feature = "egui_extras")]
+        if let Some(size) = &mut cfg.size {
+            use egui_extras::Size;
+            let scale = |range: Rangef| Rangef::new(range.min * factor, range.max * factor);
+            *size = match *size {
+                Size::Absolute { initial, range } => Size::Absolute {
+                    initial: initial * factor,
+                    range: scale(range),
+                },
+                Size::Relative { fraction, range } => Size::Relative {
+                    fraction,
+                    range: scale(range),
+                },
+                Size::Remainder { range } => Size::Remainder {
+                    range: scale(range),
+                },
+            };
+        }
+        cfg
     }
 
-    /// Show the Panel with animation with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
-    pub fn show_animated<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ctx: &'a Context,
-        index: usize,
-        is_expanded: bool,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        if let Some(cfg) = self.panels.get(index) {
-            Self::show_panel_animated(cfg.expanded(), ctx, is_expanded, content, self.name.clone())
-        } else {
-            None
-        }
+    /// Keep the content out of the screen's unsafe areas, see [`SafeArea`].
+    pub fn safe_area(mut self, safe_area: SafeArea) -> Self {
+        self.safe_area = safe_area;
+        self
     }
 
-    /// Show the Panel with animation inside a Ui with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
-    pub fn show_animated_inside<R, F: Fn(&mut egui::Ui) -> R>(
-        &self,
-        ui: &mut Ui,
-        index: usize,
-        is_expanded: bool,
-        content: F,
-    ) -> Option<egui::InnerResponse<R>> {
-        if let Some(cfg) = self.panels.get(index) {
-            Self::show_panel_animated_inside(
-                cfg.expanded(),
-                ui,
-                is_expanded,
-                content,
-                self.name.clone(),
-            )
+    /// Convenience function for a separator hit area that is comfortable to use on touch screens.
+    pub fn touch_friendly(mut self) -> Self {
+        self.resize_grab_radius = Some(TOUCH_GRAB_RADIUS);
+        self
+    }
+
+    /// Draw `image` behind the content, see [`PanelBackground`].
+    pub fn background(
+        mut self,
+        image: impl Into<egui::ImageSource<'static>>,
+        fit: BackgroundFit,
+    ) -> Self {
+        self.background = Some(PanelBackground::new(image, fit));
+        self
+    }
+
+    /// Paints the background image, `ui` being the Ui inside the panel's frame.
+    fn paint_background(&self, ui: &Ui) {
+        let Some(background) = &self.background else {
+            return;
+        };
+        let frame = self
+            .frame
+            .unwrap_or_else(|| Frame::side_top_panel(ui.style()));
+        let rect = ui.max_rect() + frame.inner_margin;
+        let image = egui::Image::new(background.image.clone())
+            .tint(background.tint)
+            .rounding(frame.rounding);
+        let image = match background.fit {
+            BackgroundFit::Stretch => image,
+            BackgroundFit::Tile(tile) => image
+                .texture_options(egui::TextureOptions::LINEAR_REPEAT)
+                .uv(Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    rect.size() / tile.max(egui::Vec2::splat(1.0)),
+                )),
+        };
+        image.paint_at(ui, rect);
+    }
+
+    /// The size this config asks for along its axis, ignoring any size the user dragged it to.
+    fn nominal_size(&self, style: &Style) -> f32 {
+        if self.side.is_lr() {
+            self.exact_width
+                .or(self.default_width)
+                .or(self.width_range.map(|r| r.0))
+                .or(self.min_width)
+                .unwrap_or(200.0)
         } else {
-            None
+            self.exact_height
+                .or(self.default_height)
+                .or(self.height_range.map(|r| r.0))
+                .or(self.min_height)
+                .unwrap_or(style.spacing.interact_size.y)
         }
     }
-}
 
-impl<'a> DynamicPanel<'a> {
-    /// Convenience function for creating a breaking panel.
-    pub fn dual(mut self, first: PanelCfg, second: PanelCfg) -> Self {
-        self.panels = vec![first, second];
-        self
+    /// The smallest size this config can be shown at along its axis.
+    fn smallest_size(&self, style: &Style) -> f32 {
+        if self.side.is_lr() {
+            self.exact_width
+                .or(self.width_range.map(|r| r.0))
+                .or(self.min_width)
+                .unwrap_or(96.0)
+        } else {
+            self.exact_height
+                .or(self.height_range.map(|r| r.0))
+                .or(self.min_height)
+                .unwrap_or(style.spacing.interact_size.y)
+        }
     }
 
-    /// Convenience function to allow a choice function between index 0 and 1. (true = 1)
-    pub fn with_threshold_function<F: Fn(&'a Context) -> bool + 'static>(mut self, f: F) -> Self {
-        let f = move |ctx| {
-            if f(ctx) {
-                1
+    /// This config with its `egui_extras::Size` resolved against `container`.
+    fn sized_for(&self, container: Rect) -> Cow<'_, Self> {
+        #[cfg(feature = "egui_extras")]
+        if let Some(size) = self.size {
+            use egui_extras::Size;
+            let available = self.side.rect_size(container);
+            let (default, range) = match size {
+                Size::Absolute { initial, range } => (initial, range),
+                Size::Relative { fraction, range } => {
+                    let size = range.clamp(fraction * available);
+                    (size, egui::Rangef::point(size))
+                }
+                Size::Remainder { range } => {
+                    let size = range.clamp(available);
+                    (size, egui::Rangef::point(size))
+                }
+            };
+            let exact = (range.min == range.max).then_some(range.min);
+            let mut cfg = self.clone();
+            if self.side.is_lr() {
+                cfg.default_width = Some(default);
+                cfg.width_range = Some((range.min, range.max));
+                cfg.exact_width = exact;
             } else {
-                0
+                cfg.default_height = Some(default);
+                cfg.height_range = Some((range.min, range.max));
+                cfg.exact_height = exact;
             }
+            return Cow::Owned(cfg);
+        }
+        let _ = container;
+        Cow::Borrowed(self)
+    }
+
+    /// This config with its unset fields taken from the [`PanelDefaults`] of `ctx`.
+    fn with_defaults(&self, ctx: &Context) -> Cow<'_, Self> {
+        let defaults = panel_defaults(ctx);
+        let mut cfg = Cow::Borrowed(self);
+        if let (None, Some(frame)) = (self.frame, defaults.frame) {
+            cfg.to_mut().frame = Some(frame);
+        }
+        if let (None, Some(resizable)) = (self.resizable, defaults.resizable) {
+            cfg.to_mut().resizable = Some(resizable);
+        }
+        if let (None, Some(show)) = (self.show_separator_line, defaults.show_separator_line) {
+            cfg.to_mut().show_separator_line = Some(show);
+        }
+        if let (None, Some(radius)) = (self.resize_grab_radius, defaults.resize_grab_radius) {
+            cfg.to_mut().resize_grab_radius = Some(radius);
+        }
+        if let (None, Some(step)) = (self.keyboard_resize_step, defaults.keyboard_resize_step) {
+            cfg.to_mut().keyboard_resize_step = Some(step);
+        }
+        cfg
+    }
+
+    /// This config with the safe-area insets added to its frame, for panels at the edge of the screen.
+    fn at_screen_edge(&self, ctx: &Context) -> Cow<'_, Self> {
+        let insets = match self.safe_area {
+            SafeArea::Ignore => return Cow::Borrowed(self),
+            SafeArea::Integration => safe_area_insets(ctx),
+            SafeArea::Custom(insets) => insets,
         };
-        self.choice_f = Some(Box::new(f));
-        self
+        let insets = self.side.edge_insets(insets);
+        if insets == Margin::ZERO {
+            return Cow::Borrowed(self);
+        }
+        let mut frame = self
+            .frame
+            .unwrap_or_else(|| Frame::side_top_panel(&ctx.style()));
+        frame.inner_margin = frame.inner_margin + insets;
+        Cow::Owned(Self {
+            frame: Some(frame),
+            ..self.clone()
+        })
     }
 
-    pub fn with_panels(mut self, panels: Vec<PanelCfg>) -> Self {
-        self.panels = panels;
-        self
+    fn grab_radius(&self, touch: bool) -> Option<f32> {
+        self.resize_grab_radius
+            .or_else(|| touch.then_some(TOUCH_GRAB_RADIUS))
     }
 
-    pub fn push_panel(&mut self, panel: PanelCfg) -> usize {
-        let index = self.panels.len();
-        self.panels.push(panel);
-        index
+    fn has_style_overrides(&self, touch: bool) -> bool {
+        self.grab_radius(touch).is_some()
     }
 
-    pub fn with_choice_function<F: Fn(&'a Context) -> usize + 'static>(
-        mut self,
-        choice_function: F,
-    ) -> Self {
-        self.choice_f = Some(Box::new(choice_function));
-        self
+    fn apply_style(&self, style: &mut Style, touch: bool) {
+        if let Some(r) = self.grab_radius(touch) {
+            style.interaction.resize_grab_radius_side = r;
+        }
     }
-}
 
-impl<'a> DynamicPanel<'a> {
-    fn build_side_panel(cfg: &SinglePanelCfg, name: impl Into<egui::Id>) -> SidePanel {
-        let side = if cfg.side == Side::Left {
+    // The egui builders are applied anew each frame and not cached: a `DynamicPanel` and its configs are usually
+    // rebuilt every frame as well, so there is nothing a cached builder could outlive the frame in.
+    pub fn apply_top_bottom(&self, mut panel: TopBottomPanel) -> TopBottomPanel {
+        if let Some(b) = self.resizable {
+            panel = panel.resizable(b);
+        }
+        if let Some(b) = self.show_separator_line {
+            panel = panel.show_separator_line(b);
+        }
+        if let Some(height) = self.default_height {
+            panel = panel.default_height(height);
+        }
+        if let Some(height) = self.min_height {
+            panel = panel.min_height(height);
+        }
+        if let Some(height) = self.max_height {
+            panel = panel.max_height(height);
+        }
+        if let Some((min, max)) = self.height_range {
+            panel = panel.height_range(min..=max);
+        }
+        if let Some(height) = self.exact_height {
+            panel = panel.exact_height(height);
+        }
+        if let Some(frame) = self.frame {
+            panel = panel.frame(frame);
+        }
+        if let Some(f) = &self.map_top_bottom {
+            panel = f(panel);
+        }
+        panel
+    }
+
+    pub fn apply_side(&self, mut panel: SidePanel) -> SidePanel {
+        if let Some(b) = self.resizable {
+            panel = panel.resizable(b);
+        }
+        if let Some(b) = self.show_separator_line {
+            panel = panel.show_separator_line(b);
+        }
+        if let Some(width) = self.default_width {
+            panel = panel.default_width(width);
+        }
+        if let Some(width) = self.min_width {
+            panel = panel.min_width(width);
+        }
+        if let Some(width) = self.max_width {
+            panel = panel.max_width(width);
+        }
+        if let Some((min, max)) = self.width_range {
+            panel = panel.width_range(min..=max);
+        }
+        if let Some(width) = self.exact_width {
+            panel = panel.exact_width(width);
+        }
+        if let Some(frame) = self.frame {
+            panel = panel.frame(frame);
+        }
+        if let Some(f) = &self.map_side_panel {
+            panel = f(panel);
+        }
+        panel
+    }
+
+    /// The egui `SidePanel` for a left or right config, configured like this crate shows it, to continue with egui
+    /// calls the crate doesn't wrap. Only the config itself is applied, not the defaults, flipping or safe area of a
+    /// [`DynamicPanel`]; `show` it yourself. `None` for top and bottom configs.
+    pub fn side_panel(&self, id: impl Into<Id>) -> Option<SidePanel> {
+        self.side.is_lr().then(|| self.build_side_panel(id))
+    }
+
+    /// Same as [`Self::side_panel`] for a top or bottom config, `None` for left and right configs.
+    pub fn top_bottom_panel(&self, id: impl Into<Id>) -> Option<TopBottomPanel> {
+        (!self.side.is_lr()).then(|| self.build_top_bottom_panel(id))
+    }
+
+    /// See [`Self::side_panel`], configs of other sides are shown on the right.
+    fn build_side_panel(&self, id: impl Into<Id>) -> SidePanel {
+        let side = if self.side == Side::Left {
             egui::panel::Side::Left
         } else {
             egui::panel::Side::Right
         };
-        let panel = SidePanel::new(side, name);
-        cfg.apply_side(panel)
+        self.apply_side(SidePanel::new(side, id))
     }
 
-    fn build_top_bottom_panel(cfg: &SinglePanelCfg, name: impl Into<egui::Id>) -> TopBottomPanel {
-        let side = if cfg.side == Side::Top {
+    /// See [`Self::top_bottom_panel`], configs of other sides are shown at the bottom.
+    fn build_top_bottom_panel(&self, id: impl Into<Id>) -> TopBottomPanel {
+        let side = if self.side == Side::Top {
             egui::panel::TopBottomSide::Top
         } else {
             egui::panel::TopBottomSide::Bottom
         };
-        let panel = TopBottomPanel::new(side, name);
-        cfg.apply_top_bottom(panel)
+        self.apply_top_bottom(TopBottomPanel::new(side, id))
     }
 
-    fn show_panel<R, F: Fn(&mut egui::Ui) -> R>(
-        cfg: &SinglePanelCfg,
-        ctx: &'a Context,
-        content: F,
-        name: impl Into<egui::Id>,
-    ) -> egui::InnerResponse<R> {
-        match cfg.side {
-            Side::Left | Side::Right => {
-                let panel = Self::build_side_panel(cfg, name);
-                panel.show(ctx, content)
-            }
-            Side::Top | Side::Bottom => {
-                let panel = Self::build_top_bottom_panel(cfg, name);
-                panel.show(ctx, content)
-            }
+    /// The sizes along its axis this config allows, as far as it sets them. The minimum egui applies to panels
+    /// without one is left to egui.
+    fn allowed_sizes(&self) -> Rangef {
+        let (min, max, range, exact) = if self.side.is_lr() {
+            (
+                self.min_width,
+                self.max_width,
+                self.width_range,
+                self.exact_width,
+            )
+        } else {
+            (
+                self.min_height,
+                self.max_height,
+                self.height_range,
+                self.exact_height,
+            )
+        };
+        match (exact, range) {
+            (Some(size), _) => Rangef::point(size),
+            (None, Some((min, max))) => Rangef::new(min, max),
+            (None, None) => Rangef::new(min.unwrap_or(0.0), max.unwrap_or(f32::INFINITY)),
         }
     }
+}
 
-    fn show_panel_inside<R, F: Fn(&mut egui::Ui) -> R>(
-        cfg: &SinglePanelCfg,
-        ui: &mut Ui,
-        content: F,
-        name: impl Into<egui::Id>,
-    ) -> egui::InnerResponse<R> {
-        match cfg.side {
-            Side::Left | Side::Right => {
-                let panel = Self::build_side_panel(cfg, name);
-                panel.show_inside(ui, content)
-            }
-            Side::Top | Side::Bottom => {
-                let panel = Self::build_top_bottom_panel(cfg, name);
-                panel.show_inside(ui, content)
-            }
+/// Whether a [`DynamicPanel`] should tune itself for touch input.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TouchMode {
+    /// Tuned for mouse pointers.
+    #[default]
+    Off,
+    /// Tuned for fingers.
+    On,
+    /// Switch to touch tuning as soon as egui has seen a touch screen.
+    Auto,
+}
+
+/// Where the safe-area insets of a docked panel come from. Only panels shown on the `Context` touch the screen
+/// edges, panels shown inside a Ui are never padded. Of the insets, only the ones of the edges the panel touches
+/// are applied, e.g. a bottom panel ignores the top inset.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SafeArea {
+    #[default]
+    Ignore,
+    /// The insets reported by the integration through [`set_safe_area_insets`].
+    Integration,
+    /// Fixed insets, e.g. for platforms where the integration doesn't report any.
+    Custom(Margin),
+}
+
+/// Whether hovering the strip of a collapsed panel previews its content, see [`DynamicPanel::peek`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Peek {
+    #[default]
+    Off,
+    /// A preview that can't be interacted with.
+    Preview,
+    /// The content can be used as if the panel was expanded, as long as the pointer stays on it.
+    Interactive,
+}
+
+/// Sizes a panel snaps to while its separator is dragged, see [`DynamicPanel::resize_snap`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResizeSnap {
+    /// Multiples of this many points, e.g. 8.0.
+    Multiple(f32),
+    /// The closest of these sizes, e.g. the widths the content looks best at.
+    Sizes(Vec<f32>),
+}
+
+impl ResizeSnap {
+    /// The size to show for a separator dragged to `size`, within `range`.
+    fn snap(&self, size: f32, range: Rangef) -> f32 {
+        let snapped = match self {
+            ResizeSnap::Multiple(step) if *step > 0.0 => (size / step).round() * step,
+            ResizeSnap::Multiple(_) => size,
+            ResizeSnap::Sizes(sizes) => sizes
+                .iter()
+                .copied()
+                .filter(|&snap| range.contains(snap))
+                .min_by(|a, b| (a - size).abs().total_cmp(&(b - size).abs()))
+                .unwrap_or(size),
+        };
+        range.clamp(snapped)
+    }
+}
+
+/// What a click outside of an expanded panel does, see [`DynamicPanel::on_outside_click`].
+/// Clicks on windows and popups, e.g. a combo box opened from the panel, never count as outside.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum OutsideClick {
+    #[default]
+    Ignore,
+    Collapse,
+    /// Collapse only panels that were expanded by [`DynamicPanel::auto_expand`] or a [`PanelGroup`] link,
+    /// e.g. a tool drawer that slid out when the pointer reached the screen edge.
+    CollapseIfAutoExpanded,
+}
+
+/// Entries of the context menu on the separator of docked configs, see [`DynamicPanel::separator_menu`].
+/// All entries are shown by default.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SeparatorMenu {
+    /// Back to the default size of the config.
+    pub reset_size: bool,
+    /// Collapse to the strip of a [`CollapsiblePanelCfg`], or hide other configs.
+    pub collapse: bool,
+    /// Hide the panel completely, including the strip. Expanding it again shows it again.
+    pub hide: bool,
+    /// Dock the panel on the opposite side, left ↔ right or top ↔ bottom.
+    pub move_to_other_side: bool,
+}
+
+impl Default for SeparatorMenu {
+    fn default() -> Self {
+        Self {
+            reset_size: true,
+            collapse: true,
+            hide: true,
+            move_to_other_side: true,
         }
     }
+}
 
-    fn show_panel_animated<R, F: Fn(&mut egui::Ui) -> R>(
-        cfg: &SinglePanelCfg,
-        ctx: &'a Context,
-        is_expanded: bool,
-        content: F,
-        name: impl Into<egui::Id>,
-    ) -> Option<egui::InnerResponse<R>> {
-        match cfg.side {
-            Side::Left | Side::Right => {
-                let panel = Self::build_side_panel(cfg, name);
-                panel.show_animated(ctx, is_expanded, content)
+/// Side of a Panel (Left, Right : Side Panel), (Top, Bottom: TopBottomPanel)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Side {
+    pub fn is_lr(&self) -> bool {
+        matches!(self, Side::Left | Side::Right)
+    }
+
+    /// The edge of `panel_rect` the separator is drawn on, expanded by `radius` across the separator.
+    fn separator_rect(&self, panel_rect: Rect, radius: f32) -> Rect {
+        let edge = match self {
+            Side::Left => Rect::from_x_y_ranges(
+                panel_rect.right()..=panel_rect.right(),
+                panel_rect.y_range(),
+            ),
+            Side::Right => {
+                Rect::from_x_y_ranges(panel_rect.left()..=panel_rect.left(), panel_rect.y_range())
             }
-            Side::Top | Side::Bottom => {
-                let panel = Self::build_top_bottom_panel(cfg, name);
-                panel.show_animated(ctx, is_expanded, content)
+            Side::Top => Rect::from_x_y_ranges(
+                panel_rect.x_range(),
+                panel_rect.bottom()..=panel_rect.bottom(),
+            ),
+            Side::Bottom => {
+                Rect::from_x_y_ranges(panel_rect.x_range(), panel_rect.top()..=panel_rect.top())
             }
+        };
+        if self.is_lr() {
+            edge.expand2(egui::vec2(radius, 0.0))
+        } else {
+            edge.expand2(egui::vec2(0.0, radius))
         }
     }
 
-    fn show_panel_animated_inside<R, F: Fn(&mut egui::Ui) -> R>(
-        cfg: &SinglePanelCfg,
-        ui: &mut Ui,
-        is_expanded: bool,
-        content: F,
-        name: impl Into<egui::Id>,
-    ) -> Option<egui::InnerResponse<R>> {
-        match cfg.side {
-            Side::Left | Side::Right => {
-                let panel = Self::build_side_panel(cfg, name);
-                panel.show_animated_inside(ui, is_expanded, content)
-            }
-            Side::Top | Side::Bottom => {
-                let panel = Self::build_top_bottom_panel(cfg, name);
-                panel.show_animated_inside(ui, is_expanded, content)
-            }
+    /// Rotation of an icon drawn for a left panel to match this side.
+    fn icon_angle(&self) -> f32 {
+        match self {
+            Side::Left => 0.0,
+            Side::Right => std::f32::consts::PI,
+            Side::Top => std::f32::consts::FRAC_PI_2,
+            Side::Bottom => -std::f32::consts::FRAC_PI_2,
         }
     }
 
-    fn show_panel_animated_between<R, F: Fn(&mut Ui, f32) -> R>(
-        cfg: &PanelCfg,
-        ctx: &'a Context,
-        is_expanded: bool,
-        content: F,
-        name: impl Into<egui::Id> + Clone,
-    ) -> Option<InnerResponse<R>> {
-        match (
-            cfg.collapsed().side().is_lr(),
-            cfg.expanded().side().is_lr(),
-        ) {
-            (true, true) => {
-                let collapsed = Self::build_side_panel(cfg.collapsed(), name.clone());
-                let expanded = Self::build_side_panel(cfg.expanded(), name);
-                SidePanel::show_animated_between(ctx, is_expanded, collapsed, expanded, content)
-            }
-            (false, false) => {
-                let collapsed = Self::build_top_bottom_panel(cfg.collapsed(), name.clone());
-                let expanded = Self::build_top_bottom_panel(cfg.expanded(), name);
-                TopBottomPanel::show_animated_between(
-                    ctx,
-                    is_expanded,
-                    collapsed,
-                    expanded,
-                    content,
-                )
-            }
-            (_, _) => None,
+    /// Rect of the given size next to the inner edge of a panel at `panel_rect`, towards the central area.
+    fn beside(&self, panel_rect: Rect, size: f32) -> Rect {
+        let r = panel_rect;
+        match self {
+            Side::Left => Rect::from_x_y_ranges(r.right()..=r.right() + size, r.y_range()),
+            Side::Right => Rect::from_x_y_ranges(r.left() - size..=r.left(), r.y_range()),
+            Side::Top => Rect::from_x_y_ranges(r.x_range(), r.bottom()..=r.bottom() + size),
+            Side::Bottom => Rect::from_x_y_ranges(r.x_range(), r.top() - size..=r.top()),
         }
     }
 
-    fn show_panel_animated_between_inside<R, F: Fn(&mut Ui, f32) -> R>(
-        cfg: &PanelCfg,
-        ui: &mut Ui,
-        is_expanded: bool,
-        content: F,
-        name: impl Into<egui::Id> + Clone,
-    ) -> Option<InnerResponse<R>> {
-        match (
-            cfg.collapsed().side().is_lr(),
-            cfg.expanded().side().is_lr(),
-        ) {
-            (true, true) => {
-                let collapsed = Self::build_side_panel(cfg.collapsed(), name.clone());
-                let expanded = Self::build_side_panel(cfg.expanded(), name);
-                Some(SidePanel::show_animated_between_inside(
-                    ui,
-                    is_expanded,
-                    collapsed,
-                    expanded,
-                    content,
-                ))
-            }
-            (false, false) => {
-                let collapsed = Self::build_top_bottom_panel(cfg.collapsed(), name.clone());
-                let expanded = Self::build_top_bottom_panel(cfg.expanded(), name);
-                Some(TopBottomPanel::show_animated_between_inside(
-                    ui,
-                    is_expanded,
-                    collapsed,
-                    expanded,
-                    content,
-                ))
-            }
-            _ => None,
+    /// `insets` without the one of the edge opposite to this side, which the panel doesn't touch.
+    fn edge_insets(&self, insets: Margin) -> Margin {
+        match self {
+            Side::Left => Margin {
+                right: 0.0,
+                ..insets
+            },
+            Side::Right => Margin {
+                left: 0.0,
+                ..insets
+            },
+            Side::Top => Margin {
+                bottom: 0.0,
+                ..insets
+            },
+            Side::Bottom => Margin { top: 0.0, ..insets },
+        }
+    }
+
+    /// The arrow keys that (shrink, grow) a panel on this side.
+    fn resize_keys(&self) -> (Key, Key) {
+        match self {
+            Side::Left => (Key::ArrowLeft, Key::ArrowRight),
+            Side::Right => (Key::ArrowRight, Key::ArrowLeft),
+            Side::Top => (Key::ArrowUp, Key::ArrowDown),
+            Side::Bottom => (Key::ArrowDown, Key::ArrowUp),
+        }
+    }
+
+    /// Resizes `rect` along this side's axis, keeping the screen edge fixed.
+    fn set_rect_size(&self, rect: &mut Rect, size: f32) {
+        match self {
+            Side::Left => rect.max.x = rect.min.x + size,
+            Side::Right => rect.min.x = rect.max.x - size,
+            Side::Top => rect.max.y = rect.min.y + size,
+            Side::Bottom => rect.min.y = rect.max.y - size,
+        }
+    }
+
+    /// Size of `rect` along this side's axis.
+    fn rect_size(&self, rect: Rect) -> f32 {
+        if self.is_lr() {
+            rect.width()
+        } else {
+            rect.height()
+        }
+    }
+
+    fn opposite(&self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+        }
+    }
+
+    /// Size of `panel_rect` if its separator was at `pos`.
+    fn size_at(&self, panel_rect: Rect, pos: egui::Pos2) -> f32 {
+        match self {
+            Side::Left => pos.x - panel_rect.left(),
+            Side::Right => panel_rect.right() - pos.x,
+            Side::Top => pos.y - panel_rect.top(),
+            Side::Bottom => panel_rect.bottom() - pos.y,
+        }
+    }
+}
+
+/// State a [`DynamicPanel`] settled in after an animation, see [`DynamicPanel::on_animation_end`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationEnd {
+    /// Index of the config that is shown.
+    pub index: usize,
+    /// Whether the panel ended up expanded or collapsed.
+    pub expanded: bool,
+}
+
+type AnimationEndFn = dyn Fn(&Context, AnimationEnd);
+type ActionBarFn = dyn Fn(&mut Ui);
+type FrameFn = dyn Fn(&Context, &DynamicPanelState) -> Frame;
+type RegionFn = dyn Fn(&Context) -> Rect;
+type PanelCfgs = SmallVec<[PanelCfg; 2]>;
+
+/// Panel that can be displayed dynamically as a `SidePanel` or `TopBottomPanel` - e.g. if the screen size is too small.
+/// Everything that has to survive between frames is kept in egui's memory (see [`DynamicPanelState`]), so the panel
+/// can be built anew every frame like any egui container.
+pub struct DynamicPanel<'a> {
+    name: String,
+    /// Inline for the common case of two configs, so they don't need an allocation when the panel is built every
+    /// frame. The name and closures capturing data are still allocated.
+    panels: PanelCfgs,
+    /// Keys of configs added with [`Self::push_panel_named`], with the index of their config.
+    keys: Vec<(Id, usize)>,
+    choice_f: Option<Box<dyn Fn(&'a egui::Context) -> Option<usize>>>,
+    key_choice_f: Option<Box<dyn Fn(&'a egui::Context) -> Id>>,
+    rect_choice_f: Option<Box<dyn Fn(Rect) -> usize>>,
+    parent: Option<Id>,
+    parent_choice_f: Option<Box<dyn Fn(usize, Rect) -> usize>>,
+    touch_mode: TouchMode,
+    modal: bool,
+    #[cfg(feature = "animation")]
+    animate_index_changes: bool,
+    #[cfg(feature = "animation")]
+    crossfade: bool,
+    #[cfg(feature = "animation")]
+    interpolate_sizes: bool,
+    #[cfg(feature = "animation")]
+    chain: SmallVec<[usize; 4]>,
+    strict: bool,
+    fallback_index: Option<usize>,
+    /// The choice function comes from [`Self::with_rules`], which falls back when no rule holds.
+    rules: bool,
+    animated: Option<bool>,
+    reduce_motion: Option<bool>,
+    size_group: Option<Id>,
+    action_bar: Option<(f32, Box<ActionBarFn>)>,
+    peek: Peek,
+    outside_click: OutsideClick,
+    expand_on_drag: Option<f32>,
+    collapse_icon: Option<PanelIcon>,
+    strip_title: Option<String>,
+    adapt_layout: bool,
+    handle_icon: Option<PanelIcon>,
+    size_readout: bool,
+    collapse_on_separator_click: bool,
+    rubber_band: bool,
+    resize_snap: Option<ResizeSnap>,
+    forget_after: Option<f64>,
+    namespace_inside: bool,
+    separator_menu: Option<SeparatorMenu>,
+    on_animation_end: Option<Box<AnimationEndFn>>,
+    frame_f: Option<Box<FrameFn>>,
+    constrain_f: Option<Box<RegionFn>>,
+    observers: Vec<Arc<dyn LayoutObserver>>,
+}
+
+impl Default for DynamicPanel<'_> {
+    /// A panel without configs, named after the location of the call, which keeps the Id stable across frames.
+    /// Two panels created at the same location (e.g. in a loop) collide, use [`DynamicPanel::new`] for those.
+    #[track_caller]
+    fn default() -> Self {
+        let location = std::panic::Location::caller();
+        Self::new(&format!(
+            "DynamicPanel@{}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        ))
+    }
+}
+
+impl<'a> DynamicPanel<'a> {
+    /// Constructor. Name will be used for the Panel Id.
+    /// Names have to be unique, panels with the same name shown in one frame are reported in debug builds.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            panels: PanelCfgs::new(),
+            keys: Vec::new(),
+            choice_f: None,
+            key_choice_f: None,
+            rect_choice_f: None,
+            parent: None,
+            parent_choice_f: None,
+            touch_mode: TouchMode::Off,
+            modal: false,
+            #[cfg(feature = "animation")]
+            animate_index_changes: false,
+            #[cfg(feature = "animation")]
+            crossfade: false,
+            #[cfg(feature = "animation")]
+            interpolate_sizes: false,
+            #[cfg(feature = "animation")]
+            chain: SmallVec::new(),
+            strict: cfg!(debug_assertions),
+            fallback_index: None,
+            rules: false,
+            animated: None,
+            reduce_motion: None,
+            size_group: None,
+            action_bar: None,
+            peek: Peek::Off,
+            outside_click: OutsideClick::Ignore,
+            expand_on_drag: None,
+            collapse_icon: None,
+            strip_title: None,
+            adapt_layout: false,
+            handle_icon: None,
+            size_readout: false,
+            collapse_on_separator_click: false,
+            rubber_band: false,
+            resize_snap: None,
+            forget_after: None,
+            namespace_inside: true,
+            separator_menu: None,
+            on_animation_end: None,
+            frame_f: None,
+            constrain_f: None,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Whether the panel is currently tuned for touch input, see [`Self::touch_mode`].
+    pub fn is_touch(&self, ctx: &Context) -> bool {
+        match self.touch_mode {
+            TouchMode::Off => false,
+            TouchMode::On => true,
+            TouchMode::Auto => ctx.input(|i| i.has_touch_screen()),
         }
     }
+
+    /// The Id used for the underlying egui panel.
+    pub fn id(&self) -> Id {
+        Id::new(&self.name)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A panel to be shown inside of this one. Its name (and thereby Id) is namespaced with the name of this panel,
+    /// and it inherits the touch, animation and strictness settings. Without a choice function of its own, the
+    /// child shows the same index as this panel, see [`Self::with_parent_choice_function`] to derive it instead.
+    pub fn child(&self, name: &str) -> DynamicPanel<'a> {
+        let mut child = DynamicPanel::new(&format!("{}/{}", self.name, name));
+        child.parent = Some(self.id());
+        child.touch_mode = self.touch_mode;
+        child.strict = self.strict;
+        child.animated = self.animated;
+        child.reduce_motion = self.reduce_motion;
+        child
+    }
+
+    /// Handle for controlling the panel from other parts of the app or other threads, see [`PanelCommands`].
+    pub fn commands(&self, ctx: &Context) -> PanelCommands {
+        commands::handle(ctx, self.id())
+    }
+
+    /// Makes the `show_dynamic*` methods show the config at `index` regardless of the choice function,
+    /// until the pin is cleared with `None`. Ignored with a warning if there is no config at `index`.
+    pub fn pin_index(&self, ctx: &Context, index: Option<usize>) {
+        if let Some(index) = index.filter(|&index| index >= self.panels.len()) {
+            log::warn!(
+                "DynamicPanel `{}` has no config at index {index}",
+                self.name
+            );
+            return;
+        }
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        state.pinned_index = index;
+        state.store(ctx, self.id());
+    }
+
+    /// Makes the `show_dynamic*` methods show the config at `index` until the choice function switches to another
+    /// config, e.g. to restore a layout without turning off the responsive choice. Ignored if there is no config
+    /// at `index`.
+    pub(crate) fn prefer_index(&self, ctx: &Context, index: usize) {
+        if index >= self.panels.len() {
+            return;
+        }
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        state.preferred = Some((index, None));
+        state.store(ctx, self.id());
+    }
+
+    /// Index of the config shown last by a `show_dynamic*` method.
+    pub fn current_index(&self, ctx: &Context) -> Option<usize> {
+        DynamicPanelState::load(ctx, self.id()).current_index()
+    }
+
+    /// Clears everything this panel keeps in egui's memory: its size, collapse state, pinned index and running
+    /// transitions. The next time it is shown it starts out like a new panel. Animations keep running in egui's
+    /// animation manager, which has no way to remove them individually.
+    pub fn forget(&self, ctx: &Context) {
+        self.forget_sizes(ctx);
+        DynamicPanelState::remove(ctx, self.id());
+    }
+
+    /// The Id of the egui panel when shown inside `ui`, see [`Self::namespace_inside`]. Recorded for
+    /// [`Self::panel_id`].
+    fn inside_id(&self, ui: &Ui) -> Id {
+        let id = if self.namespace_inside {
+            ui.id().with(&self.name)
+        } else {
+            self.id()
+        };
+        self.record_panel_id(ui.ctx(), id)
+    }
+
+    /// The Id of the egui panel when shown on the context, recorded for [`Self::panel_id`].
+    fn ctx_id(&self, ctx: &Context) -> Id {
+        self.record_panel_id(ctx, self.id())
+    }
+
+    /// Outside of the root viewport the egui panel Id includes the viewport, so egui keeps a size per viewport like
+    /// this crate keeps a state.
+    fn record_panel_id(&self, ctx: &Context, id: Id) -> Id {
+        let viewport = ctx.viewport_id();
+        let id = if viewport == egui::ViewportId::ROOT {
+            id
+        } else {
+            id.with(viewport)
+        };
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        if state.panel_id != Some(id) {
+            state.panel_id = Some(id);
+            state.store(ctx, self.id());
+        }
+        id
+    }
+
+    /// The Id of the egui panel the docked configs were shown as last, which egui stores the size under. Differs
+    /// from [`Self::id`] inside a Ui (see [`Self::namespace_inside`]) and outside of the root viewport.
+    pub(crate) fn panel_id(&self, ctx: &Context) -> Id {
+        DynamicPanelState::load(ctx, self.id())
+            .panel_id
+            .unwrap_or_else(|| self.id())
+    }
+
+    /// The state of this panel that is kept between frames.
+    pub fn state(&self, ctx: &Context) -> DynamicPanelState {
+        DynamicPanelState::load(ctx, self.id())
+    }
+
+    /// Show the Panel dynamically, based on the choice function.
+    pub fn show_dynamic<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a egui::Context,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        commands::apply(ctx, self);
+        if self.show_detached(ctx, &content) {
+            return None;
+        }
+        let index = self.choose(ctx, ctx.available_rect())?;
+        let id = self.ctx_id(ctx);
+        self.track_index(ctx, index, id);
+        #[cfg(feature = "animation")]
+        if let Some(transition) = self.running_transition(ctx, id) {
+            return self.show_transition(ctx, transition, id, &content);
+        }
+        self.show(ctx, index, content)
+    }
+
+    /// Show the Panel dynamically inside a Ui, based on the choice function.
+    pub fn show_dynamic_inside<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a Context,
+        ui: &mut Ui,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        commands::apply(ctx, self);
+        if self.show_detached(ctx, &content) {
+            return None;
+        }
+        let index = self.choose(ctx, ui.available_rect_before_wrap())?;
+        let id = self.inside_id(ui);
+        self.track_index(ctx, index, id);
+        #[cfg(feature = "animation")]
+        if let Some(transition) = self.running_transition(ctx, id) {
+            return self.show_transition_inside(ui, transition, id, &content);
+        }
+        self.show_inside(ui, index, content)
+    }
+
+    /// Moves the panel out of the layout into its own native window (an egui viewport), where the `show_dynamic*`
+    /// methods show it until it is docked again. Closing the window, or moving it back to the screen edge the panel
+    /// was docked at, docks the panel. On integrations without multiple viewports the window is an `egui::Window`
+    /// instead. The user can also tear the panel off with [`Self::tear_off_handle`].
+    pub fn detach(&self, ctx: &Context) {
+        self.set_detached(ctx, true);
+    }
+
+    /// Moves a detached panel back into the layout.
+    pub fn dock(&self, ctx: &Context) {
+        self.set_detached(ctx, false);
+    }
+
+    pub fn is_detached(&self, ctx: &Context) -> bool {
+        DynamicPanelState::load(ctx, self.id()).detached
+    }
+
+    /// Shows an auto-hiding overlay again (see [`OverlayPanelCfg::auto_hide_after`]),
+    /// e.g. when a new status message arrives.
+    pub fn reveal(&self, ctx: &Context) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        state.revealed_at = Some(ctx.input(|i| i.time));
+        state.store(ctx, self.id());
+        ctx.request_repaint();
+    }
+
+    /// Width (left/right) or height (top/bottom) of the docked panel while the user drags its separator, `None`
+    /// otherwise.
+    pub fn dragged_size(&self, ctx: &Context) -> Option<f32> {
+        let id = self.panel_id(ctx);
+        ctx.read_response(id.with("__resize"))
+            .filter(|response| response.dragged())?;
+        let rect = PanelState::load(ctx, id)?.rect;
+        Some(self.current_side(ctx).rect_size(rect))
+    }
+
+    /// How far the panel is open in its expand/collapse animation, from 0.0 (closed) to 1.0 (open), e.g. for a
+    /// canvas next to it that moves along. Without animation, or before a `show*_animated` method was called,
+    /// this is 0.0 or 1.0 depending on [`Self::is_expanded`].
+    pub fn how_expanded(&self, ctx: &Context) -> f32 {
+        let state = DynamicPanelState::load(ctx, self.id());
+        let Some(target) = state.animation_target.filter(|_| self.animates(ctx)) else {
+            let expanded = state
+                .animation_target
+                .unwrap_or_else(|| state.is_expanded());
+            return f32::from(u8::from(expanded));
+        };
+        // Same animation the egui panel (or the overlay) uses, so this only reads its current value.
+        let docked = state.current_index().and_then(|index| self.docked(index));
+        let id = match docked {
+            Some(_) => self.panel_id(ctx),
+            None => self.id(),
+        };
+        ctx.animate_bool_responsive(id.with("animation"), target)
+    }
+
+    /// Whether the panel is expanded according to the collapse state kept by this crate, which includes being
+    /// hidden by a [`PanelGroup`].
+    pub fn is_expanded(&self, ctx: &Context) -> bool {
+        DynamicPanelState::load(ctx, self.id()).is_expanded()
+    }
+
+    /// Expands or collapses the panel, see [`Self::show_dynamic_collapsible`].
+    pub fn set_expanded(&self, ctx: &Context, expanded: bool) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        state.collapsed = !expanded;
+        state.auto_expanded = false;
+        state.store(ctx, self.id());
+        if !expanded {
+            self.release_focus(ctx);
+        }
+    }
+
+    /// Expands the panel on behalf of something other than the user, e.g. the pointer reaching the screen edge.
+    /// Such panels can be collapsed again by clicking elsewhere, see [`OutsideClick::CollapseIfAutoExpanded`].
+    pub fn auto_expand(&self, ctx: &Context) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        if state.collapsed {
+            state.collapsed = false;
+            state.auto_expanded = true;
+            state.store(ctx, self.id());
+        }
+    }
+
+    /// Flips the collapse state of the panel.
+    pub fn toggle(&self, ctx: &Context) {
+        self.set_expanded(ctx, !self.is_expanded(ctx));
+    }
+
+    /// Button toggling the collapse state of the panel, to be placed e.g. in the panel's header.
+    /// Shows a chevron pointing in the direction the panel moves, or the icon set with [`Self::collapse_icon`].
+    /// Larger in [touch mode](Self::touch_mode).
+    pub fn collapse_button(&self, ui: &mut Ui) -> egui::Response {
+        let mut size = egui::Vec2::splat(ui.spacing().icon_width);
+        if self.is_touch(ui.ctx()) {
+            size = size.max(egui::Vec2::splat(TOUCH_BUTTON_SIZE));
+        }
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+        if response.clicked() {
+            self.toggle(ui.ctx());
+        }
+        if ui.is_rect_visible(rect) {
+            let state = IconState {
+                expanded: self.is_expanded(ui.ctx()),
+                side: self.current_side(ui.ctx()),
+                hovered: response.hovered(),
+            };
+            match &self.collapse_icon {
+                Some(icon) => icon.draw(ui, rect, state),
+                None => icon::paint_chevron(ui, rect, state),
+            }
+        }
+        response
+    }
+
+    /// Grip for tearing the panel off, to be placed e.g. in the panel's header. Dropping it outside the panel and
+    /// away from the screen edge the panel is docked at detaches the panel into a window at the drop position, see
+    /// [`Self::detach`].
+    pub fn tear_off_handle(&self, ui: &mut Ui) -> egui::Response {
+        let size = egui::Vec2::splat(ui.spacing().icon_width);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::drag());
+        let response = response.on_hover_cursor(egui::CursorIcon::Grab);
+        if ui.is_rect_visible(rect) {
+            let color = ui.style().interact(&response).fg_stroke.color;
+            let step = rect.width() / 4.0;
+            for (x, y) in [
+                (-1.0, -1.0),
+                (1.0, -1.0),
+                (-1.0, 0.0),
+                (1.0, 0.0),
+                (-1.0, 1.0),
+                (1.0, 1.0),
+            ] {
+                let center = rect.center() + egui::vec2(x * step / 1.5, y * step);
+                ui.painter().circle_filled(center, step / 3.0, color);
+            }
+        }
+        let ctx = ui.ctx();
+        let outside = ctx.pointer_latest_pos().filter(|&pointer| {
+            let panel = PanelState::load(ctx, self.panel_id(ctx));
+            !self.dock_band(ctx).contains(pointer)
+                && !panel.is_some_and(|p| p.rect.contains(pointer))
+        });
+        if response.dragged() {
+            ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+            if let Some(pointer) = outside {
+                // Where the window will open.
+                let preview = Rect::from_min_size(pointer, egui::vec2(320.0, 480.0));
+                let painter = ctx.layer_painter(LayerId::new(
+                    egui::Order::Tooltip,
+                    self.id().with("__tear_off"),
+                ));
+                let visuals = ui.visuals();
+                painter.rect(
+                    preview,
+                    visuals.window_rounding,
+                    visuals.selection.bg_fill.gamma_multiply(0.25),
+                    visuals.selection.stroke,
+                );
+            }
+        }
+        if let Some(pointer) = outside.filter(|_| response.drag_stopped()) {
+            self.tear_off(ctx, pointer);
+        }
+        response
+    }
+
+    /// Layout flowing along the config chosen last by a `show_dynamic*` method: top to bottom for left/right
+    /// panels, left to right for top/bottom panels. Applied to the content automatically with
+    /// [`Self::adapt_layout`].
+    pub fn flow_layout(&self, ctx: &Context) -> egui::Layout {
+        Self::layout_along(self.current_side(ctx))
+    }
+
+    /// See [`Self::flow_layout`].
+    fn layout_along(side: Side) -> egui::Layout {
+        if side.is_lr() {
+            egui::Layout::top_down(egui::Align::Min)
+        } else {
+            egui::Layout::left_to_right(egui::Align::Center).with_main_wrap(true)
+        }
+    }
+
+    /// The panel's title as a button toggling the collapse state. For left/right panels it is rotated to read
+    /// from top to bottom, so it fits on a narrow strip, and the whole rotated text is clickable.
+    pub fn title_button(&self, ui: &mut Ui, title: &str) -> egui::Response {
+        let vertical = self.current_side(ui.ctx()).is_lr();
+        let color = ui.visuals().text_color();
+        let galley = ui.painter().layout_no_wrap(
+            title.to_owned(),
+            egui::TextStyle::Button.resolve(ui.style()),
+            color,
+        );
+        let size = if vertical {
+            egui::vec2(galley.size().y, galley.size().x)
+        } else {
+            galley.size()
+        };
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+        let response = response.on_hover_cursor(egui::CursorIcon::PointingHand);
+        if response.clicked() {
+            self.toggle(ui.ctx());
+        }
+        if ui.is_rect_visible(rect) {
+            let color = ui.style().interact(&response).text_color();
+            let shape = if vertical {
+                // Rotated around the galley's top left corner, which ends up at the right of the rect.
+                egui::epaint::TextShape::new(rect.right_top(), galley, color)
+                    .with_angle(std::f32::consts::FRAC_PI_2)
+            } else {
+                egui::epaint::TextShape::new(rect.left_top(), galley, color)
+            };
+            ui.painter().add(shape);
+        }
+        response
+    }
+
+    /// Reports whether something is selected in this panel, expanding or collapsing the detail panels linked to it
+    /// with [`PanelGroup::with_detail`].
+    pub fn report_selection(&self, ctx: &Context, selected: bool) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        if state.selection != Some(selected) {
+            state.selection = Some(selected);
+            state.store(ctx, self.id());
+            ctx.request_repaint();
+        }
+    }
+
+    /// Show the Panel dynamically and animated, expanded according to the collapse state kept by this crate
+    /// (see [`Self::set_expanded`] and [`PanelToggleBar`]) instead of a flag owned by the caller.
+    /// Collapsed [`CollapsiblePanelCfg`]s leave a strip with a [`Self::collapse_button`], see also [`Self::peek`],
+    /// and collapsed overlays their [floating action button](OverlayPanelCfg::fab).
+    /// `content` is only called for a collapsed panel while it is peeked at.
+    pub fn show_dynamic_collapsible<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a Context,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        commands::apply(ctx, self);
+        self.expand_for_drag(ctx);
+        let is_expanded = self.is_expanded(ctx);
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        let (hidden, detached) = (state.hidden, state.detached);
+        if is_expanded && hidden {
+            state.hidden = false;
+            state.store(ctx, self.id());
+        }
+        if is_expanded || detached {
+            let response = self.show_dynamic_animated(ctx, is_expanded, content);
+            if let Some(response) = &response {
+                self.handle_outside_click(ctx, response.response.rect);
+            }
+            return response;
+        }
+        self.show_dynamic_animated(ctx, false, &content);
+        if hidden {
+            return None;
+        }
+        match self
+            .choose(ctx, ctx.available_rect())
+            .and_then(|index| self.panel_cfg(index))
+        {
+            Some(PanelCfg::Collapsible(cfg)) => self.show_strip(ctx, cfg, &content),
+            Some(PanelCfg::Overlay(OverlayPanelCfg { fab: Some(fab), .. })) => {
+                self.show_fab(ctx, fab, ctx.screen_rect() - safe_area_insets(ctx));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Show the Panel dynamically and animated, based on the choice function.
+    /// `content` is not called while the panel is closed, see [`Self::show_animated`].
+    pub fn show_dynamic_animated<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a Context,
+        is_expanded: bool,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        commands::apply(ctx, self);
+        if self.show_detached(ctx, &content) {
+            return None;
+        }
+        let index = self.choose(ctx, ctx.available_rect())?;
+        let id = self.ctx_id(ctx);
+        self.track_index(ctx, index, id);
+        #[cfg(feature = "animation")]
+        if let Some(transition) = self.running_transition(ctx, id).filter(|_| is_expanded) {
+            return self.show_transition(ctx, transition, id, &content);
+        }
+        self.show_animated(ctx, index, is_expanded, content)
+    }
+
+    /// Show the Panel dynamically and animated inside a Ui, based on the choice function.
+    /// `content` is not called while the panel is closed, see [`Self::show_animated`].
+    pub fn show_dynamic_animated_inside<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a Context,
+        ui: &mut Ui,
+        is_expanded: bool,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        commands::apply(ctx, self);
+        if self.show_detached(ctx, &content) {
+            return None;
+        }
+        let index = self.choose(ctx, ui.available_rect_before_wrap())?;
+        let id = self.inside_id(ui);
+        self.track_index(ctx, index, id);
+        #[cfg(feature = "animation")]
+        if let Some(transition) = self.running_transition(ctx, id).filter(|_| is_expanded) {
+            return self.show_transition_inside(ui, transition, id, &content);
+        }
+        self.show_animated_inside(ui, index, is_expanded, content)
+    }
+
+    /// Show the Panel with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
+    pub fn show<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a Context,
+        index: usize,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        self.forget_if_stale(ctx);
+        match self.panel_cfg(index)? {
+            PanelCfg::Overlay(overlay) if overlay.auto_hide_after.is_some() => {
+                self.show_overlay_animated(overlay, ctx, ctx.screen_rect(), true, content)
+            }
+            PanelCfg::Overlay(overlay) => {
+                Some(self.show_overlay(overlay, ctx, ctx.screen_rect(), 1.0, content))
+            }
+            cfg => cfg.docked_expanded().map(|docked| {
+                let content = self.docked_content(cfg, &content);
+                self.show_panel(docked, ctx, content, self.ctx_id(ctx))
+            }),
+        }
+    }
+
+    /// Show the Panel inside a Ui with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
+    pub fn show_inside<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ui: &mut Ui,
+        index: usize,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        self.forget_if_stale(ui.ctx());
+        match self.panel_cfg(index)? {
+            PanelCfg::Overlay(overlay) if overlay.auto_hide_after.is_some() => {
+                let rect = ui.max_rect();
+                self.show_overlay_animated(overlay, ui.ctx(), rect, true, content)
+            }
+            PanelCfg::Overlay(overlay) => {
+                Some(self.show_overlay(overlay, ui.ctx(), ui.max_rect(), 1.0, content))
+            }
+            cfg => cfg.docked_expanded().map(|docked| {
+                let content = self.docked_content(cfg, &content);
+                let id = self.inside_id(ui);
+                self.show_panel_inside(docked, ui, content, id)
+            }),
+        }
+    }
+
+    /// Show the Panel with animation with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
+    ///
+    /// Once the closing animation has finished, `content` is not called at all (and `None` is returned) until the
+    /// panel opens again, so heavy content costs nothing while hidden. This also holds for overlays, with reduced
+    /// motion and with [`Self::animated`] disabled.
+    pub fn show_animated<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ctx: &'a Context,
+        index: usize,
+        is_expanded: bool,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        self.forget_if_stale(ctx);
+        self.record_animation_target(ctx, is_expanded);
+        if !self.animates(ctx) {
+            if is_expanded {
+                return self.show(ctx, index, content);
+            }
+            self.end_show(ctx, None);
+            return None;
+        }
+        let response = match self.panel_cfg(index)? {
+            PanelCfg::Overlay(overlay) => {
+                self.show_overlay_animated(overlay, ctx, ctx.screen_rect(), is_expanded, content)
+            }
+            cfg => self.show_panel_animated(
+                cfg.docked_expanded()?,
+                ctx,
+                is_expanded,
+                self.docked_content(cfg, &content),
+                self.ctx_id(ctx),
+            ),
+        };
+        self.track_animation(ctx, index, is_expanded);
+        response
+    }
+
+    /// Show the Panel with animation inside a Ui with the given index for its saved configuration. If you don't need manual control, use `show_dynamic` instead.
+    /// `content` is not called while the panel is closed, see [`Self::show_animated`].
+    pub fn show_animated_inside<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        ui: &mut Ui,
+        index: usize,
+        is_expanded: bool,
+        content: F,
+    ) -> Option<egui::InnerResponse<R>> {
+        self.forget_if_stale(ui.ctx());
+        self.record_animation_target(ui.ctx(), is_expanded);
+        if !self.animates(ui.ctx()) {
+            if is_expanded {
+                return self.show_inside(ui, index, content);
+            }
+            self.end_show(ui.ctx(), None);
+            return None;
+        }
+        let response = match self.panel_cfg(index)? {
+            PanelCfg::Overlay(overlay) => {
+                let rect = ui.max_rect();
+                self.show_overlay_animated(overlay, ui.ctx(), rect, is_expanded, content)
+            }
+            cfg => self.show_panel_animated_inside(
+                cfg.docked_expanded()?,
+                ui,
+                is_expanded,
+                self.docked_content(cfg, &content),
+                self.inside_id(ui),
+            ),
+        };
+        self.track_animation(ui.ctx(), index, is_expanded);
+        response
+    }
+}
+
+impl<'a> DynamicPanel<'a> {
+    /// Preset for a status bar: docked at the bottom while the screen is at least `min_height` high, and a
+    /// toast strip floating above the bottom edge that hides after a few seconds otherwise.
+    /// Call [`Self::reveal`] when the status changes to show the toast again.
+    pub fn status_bar(name: &str, min_height: f32) -> Self {
+        Self::new(name)
+            .with_panels([
+                SinglePanelCfg::bottom().into(),
+                OverlayPanelCfg::new(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -8.0))
+                    .auto_hide_after(3.0)
+                    .into(),
+            ])
+            .with_choice_function(move |ctx| {
+                usize::from(breakpoint_rect(ctx).height() < min_height)
+            })
+    }
+
+    /// Preset for a settings or filter panel: docked on the right while the screen is at least `min_width` wide,
+    /// and a centered modal dialog with a title bar and a dimmed background otherwise.
+    /// Show it with [`Self::show_dynamic_collapsible`] and open it with [`Self::set_expanded`]. The close button,
+    /// Escape and clicks on the dimmed background collapse it again.
+    pub fn dialog(name: &str, title: impl Into<String>, min_width: f32) -> Self {
+        Self::new(name)
+            .with_panels([
+                SinglePanelCfg::right().into(),
+                OverlayPanelCfg::new(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                    .title(title)
+                    .scrim(egui::Color32::from_black_alpha(128))
+                    .into(),
+            ])
+            .with_choice_function(move |ctx| usize::from(breakpoint_rect(ctx).width() < min_width))
+            .modal(true)
+    }
+
+    /// Preset for a navigation panel: docked on the left while the screen is at least `min_width` wide, and a
+    /// sheet sliding over the bottom of the screen otherwise, which is opened by a floating action button labelled
+    /// `icon` in the bottom right corner. Show it with [`Self::show_dynamic_collapsible`]. The close button, Escape
+    /// and clicks on the dimmed background collapse the sheet again.
+    pub fn fab_sheet(name: &str, icon: impl Into<String>, min_width: f32) -> Self {
+        Self::new(name)
+            .with_panels([
+                SinglePanelCfg::left().into(),
+                OverlayPanelCfg::new(egui::Align2::CENTER_BOTTOM, egui::Vec2::ZERO)
+                    .title(name)
+                    .scrim(egui::Color32::from_black_alpha(128))
+                    .fab(icon, egui::Align2::RIGHT_BOTTOM)
+                    .into(),
+            ])
+            .with_choice_function(move |ctx| usize::from(breakpoint_rect(ctx).width() < min_width))
+            .modal(true)
+    }
+
+    /// Convenience function for creating a breaking panel.
+    pub fn dual(mut self, first: PanelCfg, second: PanelCfg) -> Self {
+        self.panels = smallvec![first, second];
+        self.keys.clear();
+        self
+    }
+
+    /// Convenience function to allow a choice function between index 0 and 1. (true = 1)
+    pub fn with_threshold_function<F: Fn(&'a Context) -> bool + 'static>(mut self, f: F) -> Self {
+        let f = move |ctx| {
+            if f(ctx) {
+                Some(1)
+            } else {
+                Some(0)
+            }
+        };
+        self.choice_f = Some(Box::new(f));
+        self
+    }
+
+    /// Switch the touch related settings at once: separators get a larger hit area unless a config sets its own
+    /// `resize_grab_radius`, the [collapse button](Self::collapse_button) gets larger, and with
+    /// [`Self::collapse_on_separator_click`] it takes a double tap to collapse. Which config is shown is up to the
+    /// choice function, e.g. with [`choice::is_touch`].
+    pub fn touch_mode(mut self, enabled: bool) -> Self {
+        self.touch_mode = if enabled {
+            TouchMode::On
+        } else {
+            TouchMode::Off
+        };
+        self
+    }
+
+    /// Set the [`TouchMode`] directly, e.g. [`TouchMode::Auto`] to only tune for touch once egui has seen a touch screen.
+    pub fn with_touch_mode(mut self, touch_mode: TouchMode) -> Self {
+        self.touch_mode = touch_mode;
+        self
+    }
+
+    /// Treat the panel's overlay configs as modal surfaces: while an overlay is open, Tab-navigation cycles
+    /// through its widgets, and focus returns to the previously focused widget once it closes. Docked configs
+    /// never hold the focus, so a preset can be modal on small screens and a plain sidebar on large ones.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    /// When the choice function switches to another config, let the outgoing panel animate closed and the
+    /// incoming one animate open instead of snapping, with the content fading along. Applies to the
+    /// `show_dynamic*` methods; the `show_dynamic_animated*` ones only while the panel is expanded.
+    #[cfg(feature = "animation")]
+    pub fn animate_index_changes(mut self, animate: bool) -> Self {
+        self.animate_index_changes = animate;
+        self
+    }
+
+    /// Fade the panel content in after the choice function switched to another config, which hides the
+    /// reflow when the content is structured very differently between layouts.
+    #[cfg(feature = "animation")]
+    pub fn crossfade(mut self, crossfade: bool) -> Self {
+        self.crossfade = crossfade;
+        self
+    }
+
+    /// When the choice function switches between two configs on the same side, animate the panel size from
+    /// the old value to the new one instead of jumping. The panel ends up at the nominal size of the new config.
+    #[cfg(feature = "animation")]
+    pub fn interpolate_sizes(mut self, interpolate: bool) -> Self {
+        self.interpolate_sizes = interpolate;
+        self
+    }
+
+    /// Orders configs on the same side into a chain, e.g. hidden → rail → full. With [`Self::interpolate_sizes`],
+    /// switching between two configs of the chain passes the sizes of all configs in between, one animation time
+    /// per step, instead of going straight to the new size.
+    #[cfg(feature = "animation")]
+    pub fn chain(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.chain = indices.into_iter().collect();
+        self
+    }
+
+    /// With `false` this panel always snaps instantly: the `show*_animated` methods open and close it without
+    /// sliding, and config switches skip [`Self::animate_index_changes`], [`Self::crossfade`] and
+    /// [`Self::interpolate_sizes`]. The rest of the app keeps animating as configured in its style.
+    /// Without the `animation` feature panels never animate. Defaults to [`PanelDefaults::animated`], or `true`.
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = Some(animated);
+        self
+    }
+
+    /// Whether this panel animates. Constant `false` without the `animation` feature, so the animation code is
+    /// optimized out.
+    fn animates(&self, ctx: &Context) -> bool {
+        cfg!(feature = "animation")
+            && self
+                .animated
+                .or_else(|| panel_defaults(ctx).animated)
+                .unwrap_or(true)
+    }
+
+    /// Overrides the app-wide [`reduce_motion`] preference for this panel. With reduced motion nothing slides or
+    /// changes size: the panel appears at its full size while its content fades in, and config switches are
+    /// instant (only [`Self::crossfade`] still fades the content).
+    pub fn reduce_motion(mut self, reduce: bool) -> Self {
+        self.reduce_motion = Some(reduce);
+        self
+    }
+
+    /// Whether this panel avoids motion, see [`Self::reduce_motion`].
+    pub fn reduces_motion(&self, ctx: &Context) -> bool {
+        self.reduce_motion.unwrap_or_else(|| reduce_motion(ctx))
+    }
+
+    /// Keeps the size of this panel in sync with all other panels of the same group, e.g. left and right inspectors
+    /// of equal width. Resizing one of them resizes the others, each within the range of its own config.
+    /// Left/right panels share their width and top/bottom panels their height.
+    pub fn size_group(mut self, group: impl Into<Id>) -> Self {
+        self.size_group = Some(group.into());
+        self
+    }
+
+    /// Content for the `show*` methods that shows the cheap `compact` content instead of `content` while a docked
+    /// config is narrower (left/right) or lower (top/bottom) than `threshold`, e.g. while it slides open, so
+    /// expensive widgets aren't laid out into a sliver. Both return the `inner` value of the `show*` method.
+    ///
+    /// ```ignore
+    /// let content = panel.compact_content(48.0, |ui| icons(ui), |ui| navigation(ui));
+    /// panel.show_dynamic_animated(ctx, is_expanded, content);
+    /// ```
+    pub fn compact_content<'c, R>(
+        &'c self,
+        threshold: f32,
+        compact: impl Fn(&mut Ui) -> R + 'c,
+        content: impl Fn(&mut Ui) -> R + 'c,
+    ) -> impl Fn(&mut Ui) -> R + 'c {
+        let id = self.id();
+        let panels = &self.panels[..];
+        move |ui| {
+            // Panels shown with a fixed index don't remember it, their smaller extent is across the panel.
+            let index = DynamicPanelState::load(ui.ctx(), id).current_index();
+            let size =
+                match index.map(|index| panels.get(index).and_then(PanelCfg::docked_expanded)) {
+                    Some(Some(cfg)) if cfg.side.is_lr() => ui.available_width(),
+                    Some(Some(_)) => ui.available_height(),
+                    Some(None) => f32::INFINITY,
+                    None => ui.available_width().min(ui.available_height()),
+                };
+            if size < threshold {
+                compact(ui)
+            } else {
+                content(ui)
+            }
+        }
+    }
+
+    /// Reserves a strip of `height` for `f` at the bottom of left/right panels and at the edge facing the central
+    /// area of top/bottom panels, e.g. for Apply/Cancel buttons. The strip stays in place while the content scrolls
+    /// or the panel is resized, the content gets the rest of the panel.
+    pub fn action_bar<F: Fn(&mut Ui) + 'static>(mut self, height: f32, f: F) -> Self {
+        self.action_bar = Some((height, Box::new(f)));
+        self
+    }
+
+    /// Expand the collapsed panel while a drag and drop payload is within `distance` points of its edge (or its
+    /// strip), so the payload can be dropped into it. It collapses again unless the payload is dropped on it.
+    /// Applies to [`Self::show_dynamic_collapsible`].
+    pub fn expand_on_drag(mut self, distance: f32) -> Self {
+        self.expand_on_drag = Some(distance);
+        self
+    }
+
+    /// Preview the expanded content over the central area while the pointer is on the strip of a collapsed
+    /// [`CollapsiblePanelCfg`], without expanding the panel. See [`Self::show_dynamic_collapsible`].
+    pub fn peek(mut self, peek: Peek) -> Self {
+        self.peek = peek;
+        self
+    }
+
+    /// What a click outside of the expanded panel does, for panels shown with [`Self::show_dynamic_collapsible`].
+    pub fn on_outside_click(mut self, policy: OutsideClick) -> Self {
+        self.outside_click = policy;
+        self
+    }
+
+    /// Title shown below the collapse button on the strip of a collapsed [`CollapsiblePanelCfg`], running along
+    /// the strip. See [`Self::title_button`].
+    pub fn strip_title(mut self, title: impl Into<String>) -> Self {
+        self.strip_title = Some(title.into());
+        self
+    }
+
+    /// Lay out the content along the panel: top to bottom in left/right configs, left to right (wrapping) in
+    /// top/bottom configs, so the same content fits a sidebar as well as a toolbar. See [`Self::flow_layout`].
+    pub fn adapt_layout(mut self, adapt: bool) -> Self {
+        self.adapt_layout = adapt;
+        self
+    }
+
+    /// Icon of the [`Self::collapse_button`].
+    pub fn collapse_icon(mut self, icon: PanelIcon) -> Self {
+        self.collapse_icon = Some(icon);
+        self
+    }
+
+    /// Icon drawn in the middle of the separator of docked configs, as a visible grip for resizing.
+    pub fn handle_icon(mut self, icon: PanelIcon) -> Self {
+        self.handle_icon = Some(icon);
+        self
+    }
+
+    /// Shows the size of the panel, in points and percent of the screen, next to the pointer while the separator
+    /// is dragged. See also [`Self::dragged_size`].
+    pub fn size_readout(mut self, size_readout: bool) -> Self {
+        self.size_readout = size_readout;
+        self
+    }
+
+    /// Clicking the separator (double-tapping it on touch screens, see [`Self::is_touch`]) collapses the panel,
+    /// like [`Self::toggle`]. Dragging still resizes it. Has an effect for panels shown with
+    /// [`Self::show_dynamic_collapsible`].
+    pub fn collapse_on_separator_click(mut self, collapse: bool) -> Self {
+        self.collapse_on_separator_click = collapse;
+        self
+    }
+
+    /// Context menu on the separator of docked configs, opened by a right click (or a long press).
+    pub fn separator_menu(mut self, menu: SeparatorMenu) -> Self {
+        self.separator_menu = Some(menu);
+        self
+    }
+
+    /// Dragging the separator past the minimum or maximum size of the panel keeps moving it with growing
+    /// resistance, and it springs back to the limit on release. Only changes how the panel looks while resizing.
+    pub fn rubber_band(mut self, rubber_band: bool) -> Self {
+        self.rubber_band = rubber_band;
+        self
+    }
+
+    /// Snaps the size of docked configs to tidy values while the user drags the separator, so the panel lands on
+    /// them. Pulling past the limits with [`Self::rubber_band`] isn't snapped.
+    pub fn resize_snap(mut self, snap: ResizeSnap) -> Self {
+        self.resize_snap = Some(snap);
+        self
+    }
+
+    /// Forgets the size of the panel once it is shown again after not having been visible for `seconds`, so it
+    /// comes back at its configured size instead of one the user chose long ago in another layout. Unlike
+    /// [`Self::forget`], the collapse state and pinned index are kept.
+    pub fn forget_after(mut self, seconds: f64) -> Self {
+        self.forget_after = Some(seconds);
+        self
+    }
+
+    /// Whether the `*_inside` methods derive the Id of the egui panel from the parent Ui, so the same panel can be
+    /// shown in several containers without their sizes colliding. On by default. The state kept by this crate
+    /// (index, collapse state, …) is shared between all of them either way.
+    pub fn namespace_inside(mut self, namespace: bool) -> Self {
+        self.namespace_inside = namespace;
+        self
+    }
+
+    /// Config shown when the choice function returns an index (or key) without config, e.g. because the configs
+    /// were loaded from a file the user edited. A warning is logged in debug builds when this happens.
+    pub fn with_fallback_index(mut self, index: usize) -> Self {
+        self.fallback_index = Some(index);
+        self
+    }
+
+    /// In strict mode, showing a config index that doesn't exist or calling a `show_dynamic*` method without a
+    /// choice function panics instead of quietly showing nothing. Enabled by default in debug builds.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Called once an expand/collapse animation of the `show*_animated` methods or a transition between configs
+    /// (see [`Self::animate_index_changes`]) has finished, e.g. to start expensive work only once the panel settled.
+    pub fn on_animation_end<F: Fn(&Context, AnimationEnd) + 'static>(mut self, f: F) -> Self {
+        self.on_animation_end = Some(Box::new(f));
+        self
+    }
+
+    /// Keeps overlays, their floating action button and the fallback window of a [detached](Self::detach) panel
+    /// inside `rect`, e.g. below the menu bar, instead of anywhere on the screen. Overlays are pinned to the
+    /// corners and edges of `rect`.
+    pub fn constrain_to(self, rect: Rect) -> Self {
+        self.constrain_to_fn(move |_| rect)
+    }
+
+    /// Same as [`Self::constrain_to`] with the rect computed every frame, e.g. from the space the menu bar left.
+    pub fn constrain_to_fn(mut self, f: impl Fn(&Context) -> Rect + 'static) -> Self {
+        self.constrain_f = Some(Box::new(f));
+        self
+    }
+
+    /// Computes the frame of the panel every frame before it is shown, e.g. to highlight its border while a drag
+    /// and drop is in progress or to follow the theme. Replaces the frames of all configs, docked and overlays.
+    pub fn with_frame_fn<F: Fn(&Context, &DynamicPanelState) -> Frame + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.frame_f = Some(Box::new(f));
+        self
+    }
+
+    /// The frame computed by [`Self::with_frame_fn`], if there is one.
+    fn dynamic_frame(&self, ctx: &Context) -> Option<Frame> {
+        let f = self.frame_f.as_ref()?;
+        Some(f(ctx, &DynamicPanelState::load(ctx, self.id())))
+    }
+
+    /// Registers an observer that is told about index switches, collapsing, resizing and moves of this panel.
+    /// Can be called multiple times, and the same observer can be registered on several panels.
+    pub fn observer(mut self, observer: Arc<dyn LayoutObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Sets the configs to choose from. Accepts a `Vec` as well as an array, which doesn't allocate for up to two
+    /// configs.
+    pub fn with_panels(mut self, panels: impl IntoIterator<Item = PanelCfg>) -> Self {
+        self.panels = panels.into_iter().collect();
+        self.keys.clear();
+        self
+    }
+
+    pub fn push_panel(&mut self, panel: PanelCfg) -> usize {
+        let index = self.panels.len();
+        self.panels.push(panel);
+        index
+    }
+
+    /// Adds a config that [`Self::with_named_choice_function`] can select by `key`, so choices don't depend on
+    /// the order of the configs. A config added earlier under the same key is no longer selected by it.
+    pub fn push_panel_named(&mut self, key: &str, panel: PanelCfg) -> usize {
+        let index = self.push_panel(panel);
+        self.set_key(Id::new(key), index);
+        index
+    }
+
+    /// Index of the config added under `key`.
+    pub fn index_of(&self, key: &str) -> Option<usize> {
+        self.key_index(Id::new(key))
+    }
+
+    /// The configs to choose from, in order.
+    pub fn panels(&self) -> &[PanelCfg] {
+        &self.panels
+    }
+
+    /// The config at `index` for editing, e.g. to change the default width of a sidebar from a settings screen.
+    pub fn panel_mut(&mut self, index: usize) -> Option<&mut PanelCfg> {
+        self.panels.get_mut(index)
+    }
+
+    /// Replaces the config at `index`, returning the previous one. `None` (and `cfg` is dropped) if there is no
+    /// config at `index`.
+    pub fn replace_panel(&mut self, index: usize, cfg: PanelCfg) -> Option<PanelCfg> {
+        Some(std::mem::replace(self.panels.get_mut(index)?, cfg))
+    }
+
+    /// Removes the config at `index`, shifting the following ones down. Choice functions keep returning indices,
+    /// so they have to be adjusted as well.
+    pub fn remove_panel(&mut self, index: usize) -> Option<PanelCfg> {
+        if index >= self.panels.len() {
+            return None;
+        }
+        self.keys.retain(|(_, i)| *i != index);
+        for (_, i) in &mut self.keys {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        Some(self.panels.remove(index))
+    }
+
+    pub fn with_choice_function<F: Fn(&'a Context) -> usize + 'static>(
+        mut self,
+        choice_function: F,
+    ) -> Self {
+        self.choice_f = Some(Box::new(move |ctx| Some(choice_function(ctx))));
+        self
+    }
+
+    /// Choice function that can also decide to show nothing, by returning `None`. Replaces
+    /// [`Self::with_choice_function`].
+    pub fn with_optional_choice_function<F: Fn(&'a Context) -> Option<usize> + 'static>(
+        mut self,
+        choice_function: F,
+    ) -> Self {
+        self.choice_f = Some(Box::new(choice_function));
+        self.rules = false;
+        self
+    }
+
+    /// Choice function built from [rules](choice::Rule): the first rule whose condition holds chooses the config,
+    /// [`Self::with_fallback_index`] is used if none does.
+    pub fn with_rules(self, rules: impl IntoIterator<Item = choice::Rule>) -> Self {
+        let rules: Vec<choice::Rule> = rules.into_iter().collect();
+        let mut panel = self.with_optional_choice_function(move |ctx| choice::choose(&rules, ctx));
+        panel.rules = true;
+        panel
+    }
+
+    /// Choice function returning the key of a config added with [`Self::push_panel_named`], e.g. `"compact"`.
+    /// Takes precedence over [`Self::with_choice_function`].
+    pub fn with_named_choice_function<F: Fn(&'a Context) -> &'static str + 'static>(
+        mut self,
+        choice_function: F,
+    ) -> Self {
+        self.key_choice_f = Some(Box::new(move |ctx| Id::new(choice_function(ctx))));
+        self
+    }
+
+    /// Choice function of a [child](Self::child) panel, getting the index the parent panel is showing and the
+    /// rect available to the child. Takes precedence over the other choice functions.
+    pub fn with_parent_choice_function<F: Fn(usize, Rect) -> usize + 'static>(
+        mut self,
+        choice_function: F,
+    ) -> Self {
+        self.parent_choice_f = Some(Box::new(choice_function));
+        self
+    }
+
+    /// Choice function that gets the rect available to the panel instead of the `Context`: the parent Ui's
+    /// available rect for the `show_dynamic*_inside` methods, and the space left by other panels otherwise.
+    /// This makes the panel respond to the size of its container rather than the window.
+    /// Takes precedence over [`Self::with_choice_function`].
+    pub fn with_rect_choice_function<F: Fn(Rect) -> usize + 'static>(
+        mut self,
+        choice_function: F,
+    ) -> Self {
+        self.rect_choice_f = Some(Box::new(choice_function));
+        self
+    }
+}
+
+impl<'a> DynamicPanel<'a> {
+    /// Runs `f` with the style overrides of `cfg` applied to the context and restores the previous style afterwards.
+    /// Contents shown by `f` have to go through [`Self::unstyled_ctx`], so the overrides only reach the separator.
+    fn with_ctx_style<T>(&self, cfg: &SinglePanelCfg, ctx: &Context, f: impl FnOnce() -> T) -> T {
+        let touch = self.is_touch(ctx);
+        if !cfg.has_style_overrides(touch) {
+            return f();
+        }
+        let previous = ctx.style();
+        ctx.style_mut(|style| cfg.apply_style(style, touch));
+        let result = f();
+        ctx.set_style(previous);
+        result
+    }
+
+    /// Runs `f` with the style overrides of `cfg` applied to the Ui and restores the previous style afterwards.
+    /// Contents shown by `f` have to go through [`Self::unstyled`], so the overrides only reach the separator.
+    fn with_ui_style<T>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ui: &mut Ui,
+        f: impl FnOnce(&mut Ui) -> T,
+    ) -> T {
+        let touch = self.is_touch(ui.ctx());
+        if !cfg.has_style_overrides(touch) {
+            return f(ui);
+        }
+        let previous = ui.style().clone();
+        cfg.apply_style(ui.style_mut(), touch);
+        let result = f(ui);
+        ui.set_style(previous);
+        result
+    }
+
+    /// Runs `content` with `previous`, the style of the Ui before [`Self::with_ui_style`] applied the overrides.
+    fn unstyled<R>(ui: &mut Ui, previous: &Arc<Style>, content: impl FnOnce(&mut Ui) -> R) -> R {
+        ui.set_style(previous.clone());
+        content(ui)
+    }
+
+    /// Runs `content` with `previous`, the style of the context before [`Self::with_ctx_style`] applied the
+    /// overrides, and puts the overrides back for the separator afterwards.
+    fn unstyled_ctx<R>(
+        ui: &mut Ui,
+        previous: &Arc<Style>,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> R {
+        let overridden = ui.ctx().style();
+        if Arc::ptr_eq(&overridden, previous) {
+            return Self::unstyled(ui, previous, content);
+        }
+        ui.ctx().set_style(previous.clone());
+        let result = Self::unstyled(ui, previous, content);
+        ui.ctx().set_style(overridden);
+        result
+    }
+
+    /// Evaluates the choice function.
+    /// The index to show, `available` is the space the panel is shown in.
+    fn choose(&self, ctx: &'a Context, available: Rect) -> Option<usize> {
+        let index = self.evaluate_choice(ctx, available);
+        if index.is_none() {
+            self.release_focus(ctx);
+        }
+        index
+    }
+
+    /// See [`Self::choose`].
+    fn evaluate_choice(&self, ctx: &'a Context, available: Rect) -> Option<usize> {
+        let available = if screen_known(ctx) {
+            available
+        } else {
+            let Some(size) = initial_screen_size(ctx) else {
+                // Any config chosen now could be the wrong one, wait for the integration to report the size.
+                ctx.request_repaint();
+                self.end_show(ctx, None);
+                return None;
+            };
+            available.intersect(Rect::from_min_size(egui::Pos2::ZERO, size))
+        };
+        let available = match screen_preview(ctx) {
+            Some(preview) => {
+                let screen = screen_rect(ctx);
+                if preview.show_boundary {
+                    let stroke = egui::Stroke::new(1.0, egui::Color32::RED);
+                    ctx.debug_painter().rect_stroke(screen, 0.0, stroke);
+                }
+                available.intersect(screen)
+            }
+            None => available,
+        };
+        // Pinned and preferred indices may be left over from a panel with more configs.
+        if let Some(index) = DynamicPanelState::load(ctx, self.id()).pinned_index {
+            return Some(self.fall_back(ctx, index));
+        }
+        let parent_index = self
+            .parent
+            .and_then(|parent| DynamicPanelState::load(ctx, parent).current_index());
+        let index = if let (Some(parent_index), Some(f)) = (parent_index, &self.parent_choice_f) {
+            f(parent_index, available)
+        } else if let Some(f) = &self.rect_choice_f {
+            f(available)
+        } else if let Some(f) = &self.key_choice_f {
+            let Some(index) = self.key_index(f(ctx)).or(self.fallback_index) else {
+                assert!(
+                    !self.strict,
+                    "The choice function of DynamicPanel `{}` returned a key without config",
+                    self.name
+                );
+                return None;
+            };
+            index
+        } else if let Some(f) = &self.choice_f {
+            let fallback = self.fallback_index.filter(|_| self.rules);
+            let Some(index) = f(ctx).or(fallback) else {
+                self.end_show(ctx, None);
+                return None;
+            };
+            index
+        } else if let Some(parent_index) = parent_index {
+            parent_index
+        } else {
+            assert!(
+                !self.strict,
+                "DynamicPanel `{}` was shown dynamically without a choice function, \
+                 set one with `with_choice_function` or use `show` with an index",
+                self.name
+            );
+            return None;
+        };
+        let index = self.fall_back(ctx, index);
+        let index = self.fall_back(ctx, self.preferred(ctx, index));
+        self.repaint_on_breakpoint(ctx, index);
+        Some(index)
+    }
+
+    /// The [preferred index](Self::prefer_index) instead of `chosen`, as long as the choice didn't change since.
+    fn preferred(&self, ctx: &Context, chosen: usize) -> usize {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        let Some((index, against)) = state.preferred else {
+            return chosen;
+        };
+        let shown = match against {
+            Some(against) if against == chosen => return index,
+            Some(_) => {
+                state.preferred = None;
+                chosen
+            }
+            None => {
+                state.preferred = Some((index, Some(chosen)));
+                index
+            }
+        };
+        state.store(ctx, self.id());
+        shown
+    }
+
+    /// The [fallback index](Self::with_fallback_index) if there is no config at `index`, `index` otherwise.
+    fn fall_back(&self, ctx: &Context, index: usize) -> usize {
+        let Some(fallback) = self.fallback_index.filter(|_| index >= self.panels.len()) else {
+            return index;
+        };
+        let last_index = DynamicPanelState::load(ctx, self.id())
+            .last_choice
+            .map(|(_, index)| index);
+        if cfg!(debug_assertions) && last_index != Some(fallback) {
+            log::warn!(
+                "DynamicPanel `{}` has no config at index {index}, falling back to index {fallback}",
+                self.name
+            );
+        }
+        fallback
+    }
+
+    fn key_index(&self, key: Id) -> Option<usize> {
+        self.keys
+            .iter()
+            .find_map(|&(k, index)| (k == key).then_some(index))
+    }
+
+    fn set_key(&mut self, key: Id, index: usize) {
+        self.keys.retain(|(k, _)| *k != key);
+        self.keys.push((key, index));
+    }
+
+    /// Requests another frame when a change of the screen size made the choice switch configs. Panels only settle
+    /// on their new size a frame later, and on the web nothing else may trigger that frame until the next input.
+    fn repaint_on_breakpoint(&self, ctx: &Context, index: usize) {
+        let id = self.id();
+        let screen_rect = screen_rect(ctx);
+        let mut state = DynamicPanelState::load(ctx, id);
+        if let Some((last_rect, last_index)) = state.last_choice {
+            if last_rect != screen_rect && last_index != index {
+                ctx.request_repaint();
+            }
+        }
+        state.last_choice = Some((screen_rect, index));
+        state.store(ctx, id);
+    }
+
+    /// The config at `index`.
+    fn panel_cfg(&self, index: usize) -> Option<&PanelCfg> {
+        let cfg = self.panels.get(index);
+        assert!(
+            cfg.is_some() || !self.strict,
+            "DynamicPanel `{}` has no config at index {index}, only {} configs are registered",
+            self.name,
+            self.panels.len()
+        );
+        cfg
+    }
+
+    fn set_detached(&self, ctx: &Context, detached: bool) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        state.detached = detached;
+        if !detached {
+            state.detached_at = None;
+            state.window_rect = None;
+        }
+        state.store(ctx, self.id());
+        ctx.request_repaint();
+    }
+
+    /// Detaches the panel into a window opening at `pointer`, see [`Self::tear_off_handle`].
+    fn tear_off(&self, ctx: &Context, pointer: egui::Pos2) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        state.detached_at = Some(pointer);
+        state.store(ctx, self.id());
+        self.detach(ctx);
+    }
+
+    /// The band along the screen edge the panel is docked at, see [`DOCK_ZONE`].
+    fn dock_band(&self, ctx: &Context) -> Rect {
+        let side = self.current_side(ctx);
+        let mut edge = ctx.screen_rect();
+        side.set_rect_size(&mut edge, 0.0);
+        edge.union(side.beside(edge, DOCK_ZONE))
+    }
+
+    /// Whether the detached window shown in the current viewport came to rest overlapping `band`, in screen
+    /// coordinates, after it was moved.
+    fn window_moved_into(&self, ctx: &Context, band: Rect) -> bool {
+        let Some(outer) = ctx.input(|i| i.viewport().outer_rect) else {
+            return false;
+        };
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        let was_moving = state.window_rect.is_some_and(|(_, moving)| moving);
+        let moving = state.window_rect.is_some_and(|(last, _)| last != outer);
+        state.window_rect = Some((outer, moving));
+        state.store(ctx, self.id());
+        if moving {
+            // Window managers don't report the end of a move, look again once the window stopped.
+            ctx.request_repaint_after_secs(0.2);
+        }
+        was_moving && !moving && outer.intersects(band)
+    }
+
+    /// Shows the content in its own window if the panel is detached, returns whether it did.
+    fn show_detached<R>(&self, ctx: &Context, content: &impl Fn(&mut Ui) -> R) -> bool {
+        if !self.is_detached(ctx) {
+            return false;
+        }
+        let id = self.id();
+        let viewport_id = egui::ViewportId::from_hash_of(id.with("__detached"));
+        let detached_at = DynamicPanelState::load(ctx, id).detached_at;
+        let band = self.dock_band(ctx);
+        // Positions of native windows are in screen coordinates, relative to this viewport's.
+        let origin = ctx
+            .input(|i| i.viewport().inner_rect)
+            .map_or(egui::Vec2::ZERO, |rect| rect.min.to_vec2());
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title(&self.name)
+            .with_inner_size([320.0, 480.0]);
+        if let Some(pos) = detached_at {
+            builder = builder.with_position(pos + origin);
+        }
+        ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                let mut open = true;
+                let mut window = egui::Window::new(&self.name)
+                    .id(id.with("__detached_window"))
+                    .open(&mut open);
+                if let Some(pos) = detached_at {
+                    window = window.default_pos(pos);
+                }
+                if let Some(f) = &self.constrain_f {
+                    window = window.constrain_to(f(ctx));
+                }
+                let response = window.show(ctx, |ui| self.add_contents(ui, id, None, content));
+                let dropped_at_edge = response.is_some_and(|r| r.response.drag_stopped())
+                    && ctx.pointer_latest_pos().is_some_and(|p| band.contains(p));
+                if !open || dropped_at_edge {
+                    self.dock(ctx);
+                }
+            } else {
+                egui::CentralPanel::default()
+                    .show(ctx, |ui| self.add_contents(ui, id, None, content));
+                if ctx.input(|i| i.viewport().close_requested())
+                    || self.window_moved_into(ctx, band.translate(origin))
+                {
+                    self.dock(ctx);
+                }
+            }
+        });
+        true
+    }
+
+    /// Collapses the panel at `panel_rect` when it was clicked outside of, depending on [`Self::on_outside_click`].
+    fn handle_outside_click(&self, ctx: &Context, panel_rect: Rect) {
+        if self.outside_click == OutsideClick::Ignore {
+            return;
+        }
+        let pass = ctx.cumulative_pass_nr();
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        let was_expanded = state
+            .outside_click_pass
+            .is_some_and(|last| last + 1 >= pass);
+        state.outside_click_pass = Some(pass);
+        state.store(ctx, self.id());
+        // The click that expanded the panel in this frame, e.g. on a toggle bar, doesn't collapse it again.
+        if !was_expanded {
+            return;
+        }
+        let Some(pos) = ctx.input(|i| {
+            i.pointer
+                .any_click()
+                .then(|| i.pointer.interact_pos())
+                .flatten()
+        }) else {
+            return;
+        };
+        let on_window = ctx
+            .layer_id_at(pos)
+            .is_some_and(|layer| layer.order != egui::Order::Background);
+        if panel_rect.contains(pos) || on_window {
+            return;
+        }
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        if self.outside_click == OutsideClick::Collapse || state.auto_expanded {
+            state.collapsed = true;
+            state.auto_expanded = false;
+            state.store(ctx, self.id());
+            ctx.request_repaint();
+        }
+    }
+
+    /// Shows the collapsed config of `cfg` as a strip with the collapse button, and the peek preview if enabled.
+    fn show_strip<R>(
+        &self,
+        ctx: &'a Context,
+        cfg: &CollapsiblePanelCfg,
+        content: &impl Fn(&mut Ui) -> R,
+    ) {
+        let id = self.id();
+        let strip = self.show_panel(
+            &cfg.collapsed,
+            ctx,
+            |ui| {
+                let Some(title) = &self.strip_title else {
+                    return self.collapse_button(ui);
+                };
+                let buttons = |ui: &mut Ui| {
+                    let response = self.collapse_button(ui);
+                    self.title_button(ui, title);
+                    response
+                };
+                if cfg.collapsed.side.is_lr() {
+                    buttons(ui)
+                } else {
+                    ui.horizontal(buttons).inner
+                }
+            },
+            self.ctx_id(ctx).with("__strip"),
+        );
+        if self.peek == Peek::Off {
+            return;
+        }
+        let side = Self::shown_side(ctx, id, cfg.expanded.side);
+        let peek_rect = side.beside(strip.response.rect, cfg.expanded.nominal_size(&ctx.style()));
+        let mut state = DynamicPanelState::load(ctx, id);
+        let pointer = ctx.pointer_hover_pos();
+        let on_strip = pointer.is_some_and(|p| strip.response.rect.contains(p));
+        let on_peek = pointer.is_some_and(|p| peek_rect.contains(p));
+        state.peeking = on_strip || (state.peeking && on_peek);
+        let peeking = state.peeking;
+        state.store(ctx, id);
+
+        let peek_id = id.with("__peek");
+        let how_open = if self.animates(ctx) && !self.reduces_motion(ctx) {
+            ctx.animate_bool_responsive(peek_id, peeking)
+        } else {
+            f32::from(u8::from(peeking))
+        };
+        if how_open == 0.0 {
+            return;
+        }
+        let mut visible = peek_rect;
+        side.set_rect_size(&mut visible, how_open * side.rect_size(peek_rect));
+        egui::Area::new(peek_id)
+            .order(egui::Order::Foreground)
+            .fixed_pos(peek_rect.min)
+            .interactable(self.peek == Peek::Interactive)
+            .show(ctx, |ui| {
+                ui.set_clip_rect(visible);
+                let frame = cfg
+                    .expanded
+                    .frame
+                    .unwrap_or_else(|| Frame::side_top_panel(ui.style()));
+                frame.show(ui, |ui| {
+                    ui.set_min_size(peek_rect.size() - frame.total_margin().sum());
+                    ui.set_max_size(peek_rect.size() - frame.total_margin().sum());
+                    content(ui);
+                });
+            });
+    }
+
+    /// `content` of a docked config, with the [`Self::action_bar`] beside it.
+    fn docked_content<'c, R>(
+        &'c self,
+        cfg: &'c PanelCfg,
+        content: &'c impl Fn(&mut Ui) -> R,
+    ) -> impl Fn(&mut Ui) -> R + 'c {
+        let content = self.split_content(cfg, content);
+        let action_bar = self.action_bar.as_ref();
+        let side = cfg.docked_expanded().map(|cfg| cfg.side);
+        let id = self.id();
+        move |ui| match action_bar {
+            Some((height, bar)) => {
+                let side = side.map(|side| Self::shown_side(ui.ctx(), id, side));
+                Self::with_action_bar(ui, side, *height, bar, &content)
+            }
+            None => content(ui),
+        }
+    }
+
+    /// Shows `bar` in a strip of `height` at the edge described in [`Self::action_bar`] and `content` in the rest.
+    fn with_action_bar<R>(
+        ui: &mut Ui,
+        side: Option<Side>,
+        height: f32,
+        bar: &ActionBarFn,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> R {
+        let rect = ui.available_rect_before_wrap();
+        let height = height.min(rect.height());
+        let (body, strip) = if side == Some(Side::Bottom) {
+            let strip = Rect::from_min_max(rect.min, egui::pos2(rect.right(), rect.top() + height));
+            (Rect::from_min_max(strip.left_bottom(), rect.max), strip)
+        } else {
+            let strip =
+                Rect::from_min_max(egui::pos2(rect.left(), rect.bottom() - height), rect.max);
+            (Rect::from_min_max(rect.min, strip.right_top()), strip)
+        };
+        let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+        let edge = if side == Some(Side::Bottom) {
+            strip.bottom()
+        } else {
+            strip.top()
+        };
+        ui.painter().hline(rect.x_range(), edge, stroke);
+
+        let layout = egui::Layout::right_to_left(egui::Align::Center);
+        ui.allocate_new_ui(UiBuilder::new().max_rect(strip).layout(layout), |ui| {
+            ui.set_clip_rect(strip.intersect(ui.clip_rect()));
+            bar(ui)
+        });
+        let inner = ui
+            .allocate_new_ui(UiBuilder::new().max_rect(body), |ui| {
+                ui.set_clip_rect(body.intersect(ui.clip_rect()));
+                content(ui)
+            })
+            .inner;
+        ui.advance_cursor_after_rect(rect);
+        inner
+    }
+
+    /// `content` shown once per region for stacked configs, and as is otherwise.
+    fn split_content<'c, R>(
+        &self,
+        cfg: &'c PanelCfg,
+        content: &'c impl Fn(&mut Ui) -> R,
+    ) -> impl Fn(&mut Ui) -> R + 'c {
+        let id = self.id();
+        move |ui| match cfg {
+            PanelCfg::Stacked(stacked) => stack::show_stacked(ui, id, stacked, content),
+            _ => content(ui),
+        }
+    }
+
+    /// Side of the config shown last, for drawing icons.
+    fn current_side(&self, ctx: &Context) -> Side {
+        let side = self
+            .current_index(ctx)
+            .and_then(|index| self.docked(index))
+            .or_else(|| self.panels.iter().find_map(PanelCfg::docked_expanded))
+            .map_or(Side::Left, |cfg| cfg.side);
+        Self::shown_side(ctx, self.id(), side)
+    }
+
+    /// The expanded docked config at `index`, if there is one.
+    fn docked(&self, index: usize) -> Option<&SinglePanelCfg> {
+        self.panels.get(index)?.docked_expanded()
+    }
+
+    /// The side the expanded docked config at `index` is shown at, see [`Self::shown_side`].
+    pub(crate) fn docked_side(&self, ctx: &Context, index: usize) -> Option<Side> {
+        Some(Self::shown_side(ctx, self.id(), self.docked(index)?.side))
+    }
+
+    /// `side` of a config, or the opposite one if the panel was moved there with the
+    /// [separator menu](Self::separator_menu). `id` is the Id of the panel.
+    pub(crate) fn shown_side(ctx: &Context, id: Id, side: Side) -> Side {
+        if DynamicPanelState::load(ctx, id).flipped {
+            side.opposite()
+        } else {
+            side
+        }
+    }
+
+    /// Shows an overlay pinned inside `container`, with its content faded to `opacity`.
+    fn show_overlay<R>(
+        &self,
+        cfg: &OverlayPanelCfg,
+        ctx: &Context,
+        container: Rect,
+        opacity: f32,
+        content: impl Fn(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let id = self.id();
+        let container = self.constrained(ctx, container);
+        let mut area = egui::Area::new(id)
+            .pivot(cfg.anchor)
+            .fixed_pos(cfg.anchor.pos_in_rect(&container) + cfg.offset);
+        if self.constrain_f.is_some() {
+            area = area.constrain_to(container);
+        }
+        if let Some(color) = cfg.scrim {
+            self.show_scrim(ctx, container, color.gamma_multiply(opacity));
+            area = area.order(egui::Order::Foreground);
+        }
+        let frame = self
+            .dynamic_frame(ctx)
+            .or(cfg.frame)
+            .unwrap_or_else(|| Frame::popup(&ctx.style()));
+        let response = cfg.apply_area(area).show(ctx, |ui| {
+            ui.multiply_opacity(opacity);
+            frame
+                .show(ui, |ui| {
+                    if let Some(title) = &cfg.title {
+                        self.title_bar(ui, title);
+                    }
+                    if self.modal {
+                        focus::trap(ui, id, |ui| self.add_contents(ui, id, None, &content))
+                    } else {
+                        self.add_contents(ui, id, None, &content)
+                    }
+                })
+                .inner
+        });
+        if cfg.is_dismissable() && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.set_expanded(ctx, false);
+        }
+        self.end_show(ctx, Some(response.response.rect));
+        response
+    }
+
+    /// Dims `container` below an overlay and collapses the panel when it is clicked.
+    fn show_scrim(&self, ctx: &Context, container: Rect, color: egui::Color32) {
+        egui::Area::new(self.id().with("__scrim"))
+            .order(egui::Order::Middle)
+            .fixed_pos(container.min)
+            .show(ctx, |ui| {
+                let response = ui.allocate_rect(container, Sense::click());
+                ui.painter().rect_filled(container, 0.0, color);
+                if response.clicked() {
+                    self.set_expanded(ctx, false);
+                }
+            });
+    }
+
+    /// Expands the collapsed panel while a drag and drop payload is near it, and collapses it again once the
+    /// payload was dropped elsewhere. See [`Self::expand_on_drag`].
+    fn expand_for_drag(&self, ctx: &Context) {
+        let Some(distance) = self.expand_on_drag else {
+            return;
+        };
+        let id = self.id();
+        let mut state = DynamicPanelState::load(ctx, id);
+        let dragging = egui::DragAndDrop::has_any_payload(ctx);
+        let pointer = ctx.pointer_latest_pos();
+        if state.drag_expanded {
+            // The payload is only gone in the frame after the drop, with the pointer still where it was dropped.
+            if !dragging {
+                let dropped_here = pointer
+                    .zip(PanelState::load(ctx, self.panel_id(ctx)))
+                    .is_some_and(|(pointer, panel)| panel.rect.contains(pointer));
+                state.drag_expanded = false;
+                state.auto_expanded = false;
+                state.collapsed = !dropped_here;
+                state.store(ctx, id);
+            }
+            return;
+        }
+        let (Some(pointer), true, true) = (pointer, dragging, state.collapsed) else {
+            return;
+        };
+        let Some(side) = state
+            .current_index()
+            .and_then(|index| self.docked_side(ctx, index))
+        else {
+            return;
+        };
+        // The strip if there is one, else the screen edge the hidden panel was docked at.
+        let edge = PanelState::load(ctx, self.panel_id(ctx).with("__strip")).map_or_else(
+            || {
+                let panel = PanelState::load(ctx, self.panel_id(ctx));
+                let mut edge = panel.map_or(ctx.screen_rect(), |p| p.rect);
+                side.set_rect_size(&mut edge, 0.0);
+                edge
+            },
+            |strip| strip.rect,
+        );
+        if edge.union(side.beside(edge, distance)).contains(pointer) {
+            self.auto_expand(ctx);
+            let mut state = DynamicPanelState::load(ctx, id);
+            state.drag_expanded = true;
+            state.store(ctx, id);
+        }
+    }
+
+    /// Floating action button of a collapsed overlay, expanding the panel when clicked.
+    fn show_fab(&self, ctx: &Context, fab: &Fab, container: Rect) {
+        let container = self.constrained(ctx, container);
+        egui::Area::new(self.id().with("__fab"))
+            .order(egui::Order::Foreground)
+            .pivot(fab.anchor)
+            .fixed_pos(fab.anchor.pos_in_rect(&container) + fab.offset)
+            .show(ctx, |ui| {
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::Vec2::splat(fab.size), Sense::click());
+                let response = response.on_hover_cursor(egui::CursorIcon::PointingHand);
+                if response.clicked() {
+                    self.set_expanded(ctx, true);
+                }
+                let visuals = ui.visuals();
+                let fill = if response.hovered() {
+                    visuals.selection.bg_fill.gamma_multiply(1.2)
+                } else {
+                    visuals.selection.bg_fill
+                };
+                let radius = fab.size / 2.0;
+                ui.painter().add(
+                    visuals
+                        .popup_shadow
+                        .as_shape(rect, egui::Rounding::same(radius)),
+                );
+                ui.painter().circle_filled(rect.center(), radius, fill);
+                ui.painter().text(
+                    rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    &fab.icon,
+                    egui::FontId::proportional(radius * 0.8),
+                    visuals.selection.stroke.color,
+                );
+            });
+    }
+
+    /// Title and close button of an overlay, see [`OverlayPanelCfg::title`].
+    fn title_bar(&self, ui: &mut Ui, title: &str) {
+        ui.horizontal(|ui| {
+            ui.strong(title);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("🗙").on_hover_text("Close").clicked() {
+                    self.set_expanded(ui.ctx(), false);
+                }
+            });
+        });
+        ui.separator();
+    }
+
+    /// Shows an overlay that fades in and out depending on `is_expanded`.
+    fn show_overlay_animated<R>(
+        &self,
+        cfg: &OverlayPanelCfg,
+        ctx: &Context,
+        container: Rect,
+        is_expanded: bool,
+        content: impl Fn(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        let is_expanded = is_expanded && self.is_revealed(cfg, ctx);
+        let how_expanded = if self.animates(ctx) {
+            ctx.animate_bool_responsive(self.id().with("animation"), is_expanded)
+        } else {
+            f32::from(u8::from(is_expanded))
+        };
+        if how_expanded == 0.0 {
+            self.end_show(ctx, None);
+            return None;
+        }
+        Some(self.show_overlay(cfg, ctx, container, how_expanded, content))
+    }
+
+    /// `container` within the region of [`Self::constrain_to`].
+    fn constrained(&self, ctx: &Context, container: Rect) -> Rect {
+        match &self.constrain_f {
+            Some(f) => container.intersect(f(ctx)),
+            None => container,
+        }
+    }
+
+    /// Whether an overlay is within its auto-hide delay. Always true for overlays that don't hide.
+    fn is_revealed(&self, cfg: &OverlayPanelCfg, ctx: &Context) -> bool {
+        let Some(delay) = cfg.auto_hide_after else {
+            return true;
+        };
+        let id = self.id();
+        let now = ctx.input(|i| i.time);
+        let mut state = DynamicPanelState::load(ctx, id);
+        let hovered = ctx.read_response(id).is_some_and(|r| r.contains_pointer());
+        let revealed_at = match state.revealed_at {
+            Some(revealed_at) if !hovered => revealed_at,
+            _ => now,
+        };
+        state.revealed_at = Some(revealed_at);
+        let shown_at = state
+            .switched_at
+            .map_or(revealed_at, |s| s.max(revealed_at));
+        state.store(ctx, id);
+        let remaining = delay as f64 - (now - shown_at);
+        if remaining > 0.0 {
+            ctx.request_repaint_after_secs(remaining as f32);
+        }
+        remaining > 0.0
+    }
+
+    /// Remembers the chosen index, and starts a transition when it changed. `panel_id` is the Id of the egui panel.
+    #[cfg_attr(not(feature = "animation"), allow(unused_variables))]
+    fn track_index(&self, ctx: &Context, index: usize, panel_id: Id) {
+        let id = self.id();
+        let mut state = DynamicPanelState::load(ctx, id);
+        let switched_from = state.index.replace(index).filter(|&from| from != index);
+        if switched_from.is_some() {
+            state.switched_at = Some(ctx.input(|i| i.time));
+        }
+        #[cfg(feature = "animation")]
+        if !self.animates(ctx)
+            || self.reduces_motion(ctx)
+            || !(self.animate_index_changes || self.interpolate_sizes)
+        {
+            state.transition = None;
+        } else if let Some(from) = switched_from {
+            state.transition = self.start_transition(ctx, from, index, panel_id);
+        }
+        state.store(ctx, id);
+    }
+
+    /// Transition from the config at `from` to the one at `to`, if both are docked. Everything else switches
+    /// instantly.
+    #[cfg(feature = "animation")]
+    fn start_transition(
+        &self,
+        ctx: &Context,
+        from: usize,
+        to: usize,
+        panel_id: Id,
+    ) -> Option<Transition> {
+        let from_cfg = self.docked(from)?;
+        let to_cfg = self.docked(to)?;
+        let from_size = PanelState::load(ctx, panel_id).map_or_else(
+            || from_cfg.nominal_size(&ctx.style()),
+            |p| from_cfg.side.rect_size(p.rect),
+        );
+        let path = self.chain_path(from, to);
+        let same_side = path
+            .iter()
+            .all(|&i| self.docked(i).is_some_and(|cfg| cfg.side == to_cfg.side));
+        let interpolate = self.interpolate_sizes && same_side;
+        // Closing and reopening across sides is the index change animation, which may be off.
+        (interpolate || self.animate_index_changes).then(|| Transition {
+            from,
+            to,
+            from_size,
+            to_size: to_cfg.nominal_size(&ctx.style()),
+            interpolate,
+            start_time: ctx.input(|i| i.time),
+            steps: if interpolate {
+                path.len() as u32 - 1
+            } else {
+                1
+            },
+        })
+    }
+
+    /// The transition that is still running, if any. A transition that ran its time is finished here: the panel
+    /// lands on the new size and [`Self::on_animation_end`] is told.
+    #[cfg(feature = "animation")]
+    fn running_transition(&self, ctx: &Context, panel_id: Id) -> Option<Transition> {
+        let id = self.id();
+        let mut state = DynamicPanelState::load(ctx, id);
+        let mut finished = None;
+        if let Some(transition) = state.transition {
+            if transition.progress(ctx) >= 1.0 {
+                state.transition = None;
+                finished = Some(transition.to);
+                if transition.interpolate {
+                    // Land exactly on the new size, the interpolation frames stored intermediate ones.
+                    if let (Some(PanelState { mut rect }), Some(to)) =
+                        (PanelState::load(ctx, panel_id), self.docked(transition.to))
+                    {
+                        to.side.set_rect_size(&mut rect, transition.to_size);
+                        ctx.data_mut(|d| d.insert_persisted(panel_id, PanelState { rect }));
+                    }
+                }
+            } else {
+                ctx.request_repaint();
+            }
+        }
+        let transition = state.transition;
+        state.store(ctx, id);
+        if let (Some(index), Some(f)) = (finished, &self.on_animation_end) {
+            f(
+                ctx,
+                AnimationEnd {
+                    index,
+                    expanded: true,
+                },
+            );
+        }
+        transition
+    }
+
+    /// Forgets the panel if it wasn't visible for longer than [`Self::forget_after`]. Called before showing it.
+    fn forget_if_stale(&self, ctx: &Context) {
+        let Some(after) = self.forget_after else {
+            return;
+        };
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        if state
+            .shown_at
+            .is_some_and(|shown_at| ctx.input(|i| i.time) - shown_at > after)
+        {
+            // Collapse state and pins are the user's choice, only the sizes are stale.
+            self.forget_sizes(ctx);
+            #[cfg(feature = "animation")]
+            {
+                state.transition = None;
+            }
+            state.shown_at = None;
+            state.store(ctx, self.id());
+        }
+    }
+
+    /// Removes the sizes egui and this crate stored for the panel.
+    fn forget_sizes(&self, ctx: &Context) {
+        let id = self.id();
+        // Inside a Ui the egui panel may have another Id, see `Self::namespace_inside`.
+        let ids = [id, self.panel_id(ctx)];
+        ctx.data_mut(|d| {
+            for panel_id in ids {
+                for egui_id in [
+                    panel_id,
+                    panel_id.with("__strip"),
+                    panel_id.with("__transition"),
+                ] {
+                    d.remove::<PanelState>(egui_id);
+                }
+                d.remove::<f32>(panel_id.with("__rubber_band"));
+            }
+            d.remove::<f32>(id.with("__stack"));
+        });
+    }
+
+    /// Remembers whether a `show*_animated` call opens or closes the panel, for [`Self::how_expanded`].
+    fn record_animation_target(&self, ctx: &Context, is_expanded: bool) {
+        let mut state = DynamicPanelState::load(ctx, self.id());
+        if state.animation_target != Some(is_expanded) {
+            state.animation_target = Some(is_expanded);
+            state.store(ctx, self.id());
+        }
+    }
+
+    /// Reports the end of an expand/collapse animation to [`Self::on_animation_end`].
+    fn track_animation(&self, ctx: &Context, index: usize, is_expanded: bool) {
+        let Some(f) = &self.on_animation_end else {
+            return;
+        };
+        let id = self.id();
+        // Same animation the egui panel (or the overlay) uses, so this only reads its current value.
+        let how_expanded = ctx.animate_bool_responsive(id.with("animation"), is_expanded);
+        let animating = how_expanded > 0.0 && how_expanded < 1.0;
+        let mut state = DynamicPanelState::load(ctx, id);
+        let was_animating = std::mem::replace(&mut state.animating, animating);
+        state.store(ctx, id);
+        if was_animating && !animating {
+            f(
+                ctx,
+                AnimationEnd {
+                    index,
+                    expanded: is_expanded,
+                },
+            );
+        }
+    }
+
+    /// Configs passed when switching from `from` to `to`, both included: the configs in between on the
+    /// [chain](Self::chain), or just the two of them.
+    #[cfg(feature = "animation")]
+    fn chain_path(&self, from: usize, to: usize) -> SmallVec<[usize; 4]> {
+        let position = |index| self.chain.iter().position(|&i| i == index);
+        match (position(from), position(to)) {
+            (Some(a), Some(b)) if a <= b => self.chain[a..=b].iter().copied().collect(),
+            (Some(a), Some(b)) => self.chain[b..=a].iter().rev().copied().collect(),
+            _ => smallvec![from, to],
+        }
+    }
+
+    /// Config and size to show while `transition` interpolates sizes. Along a chain, the size passes the nominal
+    /// sizes of the configs in between, each of which is shown while the size approaches it.
+    #[cfg(feature = "animation")]
+    fn interpolation(
+        &self,
+        ctx: &Context,
+        transition: Transition,
+    ) -> Option<(&SinglePanelCfg, f32)> {
+        if !transition.interpolate {
+            return None;
+        }
+        if transition.steps <= 1 {
+            return Some((
+                self.docked(transition.to)?,
+                transition.interpolated_size(ctx),
+            ));
+        }
+        let path = self.chain_path(transition.from, transition.to);
+        let size_at = |step: usize| match step {
+            0 => Some(transition.from_size),
+            _ if step == path.len() - 1 => Some(transition.to_size),
+            _ => Some(self.docked(path[step])?.nominal_size(&ctx.style())),
+        };
+        let position = transition.progress(ctx) * transition.steps as f32;
+        let step = (position as usize).min(path.len() - 2);
+        let size = egui::lerp(size_at(step)?..=size_at(step + 1)?, position - step as f32);
+        Some((self.docked(path[step + 1])?, size))
+    }
+
+    /// Which config to show as a stand-in during `transition`, and how far it is open.
+    /// The first half closes the outgoing config, the second half opens the incoming one.
+    #[cfg(feature = "animation")]
+    fn transition_frame(
+        &self,
+        ctx: &Context,
+        transition: Transition,
+    ) -> Option<(&SinglePanelCfg, f32)> {
+        let t = transition.progress(ctx);
+        if t < 0.5 {
+            Some((self.docked(transition.from)?, 1.0 - 2.0 * t))
+        } else {
+            Some((self.docked(transition.to)?, 2.0 * t - 1.0))
+        }
+    }
+
+    /// Fully open size of `cfg` during `transition`.
+    #[cfg(feature = "animation")]
+    fn transition_size(&self, ctx: &Context, transition: Transition, cfg: &SinglePanelCfg) -> f32 {
+        if transition.progress(ctx) < 0.5 {
+            transition.from_size
+        } else {
+            cfg.nominal_size(&ctx.style())
+        }
+    }
+
+    /// Shows `transition` in place of the config it switches to: at the interpolated size, or as a placeholder of
+    /// the outgoing config closing and the incoming one opening. `id` is the Id of the egui panel.
+    #[cfg(feature = "animation")]
+    fn show_transition<R>(
+        &self,
+        ctx: &Context,
+        transition: Transition,
+        id: Id,
+        content: &impl Fn(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        if let Some((cfg, size)) = self.interpolation(ctx, transition) {
+            return self.show_sized(cfg, ctx, id, size, |ui| {
+                self.add_contents(ui, id, Some(cfg.side), content)
+            });
+        }
+        let (cfg, openness) = self.transition_frame(ctx, transition)?;
+        let size = openness * self.transition_size(ctx, transition, cfg);
+        self.show_sized(cfg, ctx, id.with("__transition"), size, |ui| {
+            Self::fade_placeholder(ui, openness, content)
+        })
+    }
+
+    /// Same as [`Self::show_transition`] inside a Ui.
+    #[cfg(feature = "animation")]
+    fn show_transition_inside<R>(
+        &self,
+        ui: &mut Ui,
+        transition: Transition,
+        id: Id,
+        content: &impl Fn(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        if let Some((cfg, size)) = self.interpolation(ui.ctx(), transition) {
+            return self.show_sized_inside(cfg, ui, id, size, |ui| {
+                self.add_contents(ui, id, Some(cfg.side), content)
+            });
+        }
+        let (cfg, openness) = self.transition_frame(ui.ctx(), transition)?;
+        let size = openness * self.transition_size(ui.ctx(), transition, cfg);
+        self.show_sized_inside(cfg, ui, id.with("__transition"), size, |ui| {
+            Self::fade_placeholder(ui, openness, content)
+        })
+    }
+
+    /// Content of a placeholder, faded as far as the placeholder is open.
+    #[cfg(feature = "animation")]
+    fn fade_placeholder<R>(ui: &mut Ui, openness: f32, content: &impl Fn(&mut Ui) -> R) -> R {
+        ui.multiply_opacity(openness);
+        content(ui)
+    }
+
+    /// Shows the content sliding from where it was to where the panel is now, if the panel jumped there since the
+    /// previous frame because its [`PanelGroup`] hid a panel or reordered them, see
+    /// [`PanelGroup::animate_reordering`]. The panel itself is in its new place right away.
+    #[cfg(feature = "animation")]
+    fn slide_content<R>(&self, ui: &mut Ui, content: impl FnOnce(&mut Ui) -> R) -> R {
+        let ctx = ui.ctx().clone();
+        let id = self.id();
+        let mut state = DynamicPanelState::load(&ctx, id);
+        if !state.slides || !self.animates(&ctx) || self.reduces_motion(&ctx) {
+            return content(ui);
+        }
+        let now = ctx.input(|i| i.time);
+        let duration = ctx.style().animation_time as f64;
+        let offset = |slide: Option<(egui::Vec2, f64)>| match slide {
+            Some((from, start)) if now - start < duration => {
+                let t = ((now - start) / duration) as f32;
+                from * (1.0 - egui::emath::easing::cubic_out(t))
+            }
+            _ => egui::Vec2::ZERO,
+        };
+        let rect = ui.max_rect();
+        let mut moving = false;
+        if let Some((last, was_moving)) = state.content_rect {
+            let jump = last.min - rect.min;
+            moving = jump != egui::Vec2::ZERO;
+            let reordered = state
+                .reordered_pass
+                .is_some_and(|pass| ctx.cumulative_pass_nr() <= pass + 1);
+            // Panels also move continuously while a neighbor animates, only sudden jumps slide.
+            if reordered
+                && !was_moving
+                && last.size() == rect.size()
+                && jump.length() >= SLIDE_MIN_JUMP
+            {
+                state.slide = Some((jump + offset(state.slide), now));
+            }
+        }
+        state.content_rect = Some((rect, moving));
+        let offset = offset(state.slide);
+        if offset == egui::Vec2::ZERO {
+            state.slide = None;
+        }
+        state.store(&ctx, id);
+        if offset == egui::Vec2::ZERO {
+            return content(ui);
+        }
+        ctx.request_repaint();
+        let mut child = ui.new_child(UiBuilder::new().max_rect(rect.translate(offset)));
+        child.set_clip_rect(rect.intersect(ui.clip_rect()));
+        let inner = content(&mut child);
+        // Take up the space of the content without the offset, so the panel keeps its size.
+        ui.advance_cursor_after_rect(child.min_rect().translate(-offset));
+        inner
+    }
+
+    /// Opacity of the content while it fades in after a config switch.
+    #[cfg(feature = "animation")]
+    fn content_opacity(&self, ctx: &Context) -> f32 {
+        if !self.animates(ctx) {
+            return 1.0;
+        }
+        let Some(switched_at) = DynamicPanelState::load(ctx, self.id()).switched_at else {
+            return 1.0;
+        };
+        let duration = ctx.style().animation_time;
+        if duration <= 0.0 {
+            return 1.0;
+        }
+        let t = ((ctx.input(|i| i.time) - switched_at) as f32 / duration).clamp(0.0, 1.0);
+        if t < 1.0 {
+            ctx.request_repaint();
+        }
+        t
+    }
+
+    /// Shows `cfg` non-resizable with the given size, e.g. as a stand-in while animating.
+    #[cfg(feature = "animation")]
+    fn show_sized<R>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ctx: &Context,
+        id: Id,
+        size: f32,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        if size <= 0.0 {
+            return None;
+        }
+        let cfg = self.placed(ctx, Cow::Borrowed(cfg));
+        let cfg = &*cfg.at_screen_edge(ctx);
+        let add_contents = |ui: &mut Ui| {
+            cfg.paint_background(ui);
+            add_contents(ui)
+        };
+        let response = match cfg.side {
+            Side::Left | Side::Right => cfg
+                .build_side_panel(id)
+                .resizable(false)
+                .exact_width(size)
+                .show(ctx, add_contents),
+            Side::Top | Side::Bottom => cfg
+                .build_top_bottom_panel(id)
+                .resizable(false)
+                .exact_height(size)
+                .show(ctx, add_contents),
+        };
+        self.record_drawn(ctx, response.response.rect);
+        Some(response)
+    }
+
+    /// Same as [`Self::show_sized`] inside a Ui.
+    #[cfg(feature = "animation")]
+    fn show_sized_inside<R>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ui: &mut Ui,
+        id: Id,
+        size: f32,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        if size <= 0.0 {
+            return None;
+        }
+        let cfg = &*self.placed(ui.ctx(), Cow::Borrowed(cfg));
+        let add_contents = |ui: &mut Ui| {
+            cfg.paint_background(ui);
+            add_contents(ui)
+        };
+        let response = match cfg.side {
+            Side::Left | Side::Right => cfg
+                .build_side_panel(id)
+                .resizable(false)
+                .exact_width(size)
+                .show_inside(ui, add_contents),
+            Side::Top | Side::Bottom => cfg
+                .build_top_bottom_panel(id)
+                .resizable(false)
+                .exact_height(size)
+                .show_inside(ui, add_contents),
+        };
+        self.record_drawn(ui.ctx(), response.response.rect);
+        Some(response)
+    }
+
+    /// Adds the user content to a panel Ui, wrapped in everything the panel is configured for. `side` is the side
+    /// of the docked config drawn, `None` for overlays and detached windows, which flow from top to bottom.
+    fn add_contents<R>(
+        &self,
+        ui: &mut Ui,
+        id: Id,
+        side: Option<Side>,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> R {
+        if self.forget_after.is_some() {
+            let mut state = DynamicPanelState::load(ui.ctx(), self.id());
+            state.shown_at = Some(ui.input(|i| i.time));
+            state.store(ui.ctx(), self.id());
+        }
+        #[cfg(debug_assertions)]
+        collision::register(ui.ctx(), id, &self.name, ui.max_rect());
+        #[cfg(feature = "animation")]
+        if self.crossfade {
+            ui.multiply_opacity(self.content_opacity(ui.ctx()));
+        }
+        #[cfg(feature = "animation")]
+        let content = |ui: &mut Ui| self.slide_content(ui, content);
+        if self.adapt_layout {
+            let layout = Self::layout_along(side.unwrap_or(Side::Left));
+            ui.with_layout(layout, content).inner
+        } else {
+            content(ui)
+        }
+    }
+
+    /// Returns the focus trapped by a [modal](Self::modal) overlay that is closed now.
+    fn release_focus(&self, ctx: &Context) {
+        if self.modal {
+            focus::release(ctx, self.id());
+        }
+    }
+
+    /// Bookkeeping after a panel was shown, or could have been shown. `drawn` is the rect of the panel if it was
+    /// drawn.
+    fn end_show(&self, ctx: &Context, drawn: Option<Rect>) {
+        if let Some(rect) = drawn {
+            self.record_drawn(ctx, rect);
+        }
+        if self.modal {
+            focus::release_if_closed(ctx, self.id());
+        }
+        observer::notify(ctx, self, self.id().with("__observed"), &self.observers);
+    }
+
+    /// Remembers that the panel was drawn at `rect` in this pass, for [`testing::LayoutProbe::shown`].
+    #[cfg_attr(not(any(test, feature = "testing")), allow(unused_variables))]
+    fn record_drawn(&self, ctx: &Context, rect: Rect) {
+        #[cfg(any(test, feature = "testing"))]
+        testing::record_drawn(ctx, self, rect);
+    }
+
+    fn has_separator_interaction(&self, cfg: &SinglePanelCfg) -> bool {
+        cfg.keyboard_resize_step.is_some()
+            || self.handle_icon.is_some()
+            || self.size_readout
+            || self.collapse_on_separator_click
+            || self.separator_menu.is_some()
+    }
+
+    /// Same as [`Self::separator_interaction`] for a panel shown directly on the context.
+    fn separator_interaction_ctx(
+        &self,
+        cfg: &SinglePanelCfg,
+        ctx: &Context,
+        id: Id,
+        panel_rect: Rect,
+    ) {
+        if !self.has_separator_interaction(cfg) {
+            return;
+        }
+        let ui = Ui::new(
+            ctx.clone(),
+            LayerId::background(),
+            id.with("__separator_ui"),
+            UiBuilder::new().max_rect(ctx.screen_rect()),
+        );
+        self.separator_interaction(cfg, &ui, id, panel_rect);
+    }
+
+    /// Interactions layered on top of the separator of a panel that was just shown.
+    fn separator_interaction(&self, cfg: &SinglePanelCfg, ui: &Ui, id: Id, panel_rect: Rect) {
+        if !self.has_separator_interaction(cfg) {
+            return;
+        }
+        let side = cfg.side;
+        let radius = ui.style().interaction.resize_grab_radius_side;
+        let separator_rect = side.separator_rect(panel_rect, radius);
+
+        if let Some(icon) = &self.handle_icon {
+            let rect = Rect::from_center_size(
+                separator_rect.center(),
+                egui::Vec2::splat(ui.spacing().icon_width),
+            );
+            let state = IconState {
+                expanded: true,
+                side,
+                hovered: ui.rect_contains_pointer(separator_rect),
+            };
+            icon.draw(ui, rect, state);
+        }
+
+        if self.size_readout {
+            Self::size_readout_label(ui.ctx(), side, id, panel_rect);
+        }
+
+        let clicks = cfg.keyboard_resize_step.is_some()
+            || self.collapse_on_separator_click
+            || self.separator_menu.is_some();
+        let response =
+            clicks.then(|| ui.interact(separator_rect, id.with("__separator"), Sense::click()));
+        if let Some(response) = response
+            .as_ref()
+            .filter(|_| self.collapse_on_separator_click)
+        {
+            let collapse = if self.is_touch(ui.ctx()) {
+                response.double_clicked()
+            } else {
+                response.clicked()
+            };
+            if collapse {
+                self.set_expanded(ui.ctx(), false);
+                return;
+            }
+        }
+        if let (Some(menu), Some(response)) = (self.separator_menu, &response) {
+            response.context_menu(|ui| self.separator_menu_ui(ui, menu, id));
+        }
+
+        if let (Some(step), Some(response)) = (cfg.keyboard_resize_step, response) {
+            if response.clicked() {
+                response.request_focus();
+            }
+            if response.has_focus() {
+                ui.memory_mut(|m| {
+                    m.set_focus_lock_filter(
+                        response.id,
+                        EventFilter {
+                            horizontal_arrows: true,
+                            vertical_arrows: true,
+                            ..Default::default()
+                        },
+                    )
+                });
+                let (shrink, grow) = side.resize_keys();
+                let steps = ui.input_mut(|i| {
+                    i.count_and_consume_key(Modifiers::NONE, grow) as f32
+                        - i.count_and_consume_key(Modifiers::NONE, shrink) as f32
+                });
+                if steps != 0.0 {
+                    let mut rect = panel_rect;
+                    let size = (side.rect_size(rect) + steps * step).max(0.0);
+                    side.set_rect_size(&mut rect, size);
+                    // The panel clamps the stored size to its configured range on the next frame.
+                    ui.data_mut(|d| d.insert_persisted(id, PanelState { rect }));
+                    ui.ctx().request_repaint();
+                }
+                ui.painter().rect_stroke(
+                    side.separator_rect(panel_rect, 1.0),
+                    0.0,
+                    ui.visuals().selection.stroke,
+                );
+            }
+        }
+    }
+
+    /// The label of [`Self::size_readout`], shown while the separator of the panel with `id` is dragged.
+    fn size_readout_label(ctx: &Context, side: Side, id: Id, panel_rect: Rect) {
+        let dragged = ctx
+            .read_response(id.with("__resize"))
+            .is_some_and(|response| response.dragged());
+        let Some(pointer) = ctx.pointer_latest_pos().filter(|_| dragged) else {
+            return;
+        };
+        let size = side.rect_size(panel_rect);
+        let screen = side.rect_size(ctx.screen_rect());
+        let percent = if screen > 0.0 {
+            size / screen * 100.0
+        } else {
+            0.0
+        };
+        egui::Area::new(id.with("__size_readout"))
+            .order(egui::Order::Tooltip)
+            .interactable(false)
+            .fixed_pos(pointer + egui::vec2(12.0, 12.0))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(format!("{size:.0} pt · {percent:.0} %"));
+                });
+            });
+    }
+
+    /// `cfg` with its size fixed past its limits while the separator is pulled there, or springing back after,
+    /// see [`Self::rubber_band`].
+    fn rubber_banded<'c>(
+        &self,
+        ctx: &Context,
+        cfg: Cow<'c, SinglePanelCfg>,
+        id: Id,
+    ) -> Cow<'c, SinglePanelCfg> {
+        if !self.rubber_band || cfg.resizable == Some(false) {
+            return cfg;
+        }
+        let Some(PanelState { rect }) = PanelState::load(ctx, id) else {
+            return cfg;
+        };
+        let range = cfg.allowed_sizes();
+        let band_id = id.with("__rubber_band");
+        let dragged = ctx
+            .read_response(id.with("__resize"))
+            .is_some_and(|response| response.dragged());
+        let size = match ctx.pointer_interact_pos().filter(|_| dragged) {
+            Some(pointer) => {
+                let wanted = cfg.side.size_at(rect, pointer);
+                let limit = range.clamp(wanted);
+                let overshoot = (wanted - limit).abs();
+                let stretch = RUBBER_BAND_DISTANCE
+                    * (1.0 - 1.0 / (overshoot / RUBBER_BAND_DISTANCE * 0.55 + 1.0));
+                let size = limit + (wanted - limit).signum() * stretch;
+                // Tracks the stretched size, so the spring back starts from there.
+                ctx.animate_value_with_time(band_id, size, 0.0);
+                if overshoot == 0.0 {
+                    ctx.data_mut(|d| d.remove::<f32>(band_id));
+                    return cfg;
+                }
+                ctx.data_mut(|d| d.insert_temp(band_id, limit));
+                size
+            }
+            None => {
+                let Some(limit) = ctx.data(|d| d.get_temp::<f32>(band_id)) else {
+                    return cfg;
+                };
+                let time = if self.animates(ctx) {
+                    ctx.style().animation_time
+                } else {
+                    0.0
+                };
+                let size = ctx.animate_value_with_time(band_id, limit, time);
+                if size == limit {
+                    ctx.data_mut(|d| d.remove::<f32>(band_id));
+                }
+                size
+            }
+        };
+        let mut cfg = cfg.into_owned();
+        if cfg.side.is_lr() {
+            cfg.exact_width = Some(size);
+        } else {
+            cfg.exact_height = Some(size);
+        }
+        Cow::Owned(cfg)
+    }
+
+    /// `cfg` with its size fixed to the snapped one while the separator is dragged, see [`Self::resize_snap`].
+    fn snapped<'c>(
+        &self,
+        ctx: &Context,
+        cfg: Cow<'c, SinglePanelCfg>,
+        id: Id,
+    ) -> Cow<'c, SinglePanelCfg> {
+        let Some(snap) = &self.resize_snap else {
+            return cfg;
+        };
+        if cfg.resizable == Some(false) {
+            return cfg;
+        }
+        let dragged = ctx
+            .read_response(id.with("__resize"))
+            .is_some_and(|response| response.dragged());
+        let (Some(PanelState { rect }), Some(pointer)) = (
+            PanelState::load(ctx, id),
+            ctx.pointer_interact_pos().filter(|_| dragged),
+        ) else {
+            return cfg;
+        };
+        let range = cfg.allowed_sizes();
+        let wanted = cfg.side.size_at(rect, pointer);
+        if !range.contains(wanted) {
+            return cfg;
+        }
+        let size = snap.snap(wanted, range);
+        let mut cfg = cfg.into_owned();
+        if cfg.side.is_lr() {
+            cfg.exact_width = Some(size);
+        } else {
+            cfg.exact_height = Some(size);
+        }
+        Cow::Owned(cfg)
+    }
+
+    /// Entries of the [separator menu](Self::separator_menu) of the panel with the egui Id `id`.
+    fn separator_menu_ui(&self, ui: &mut Ui, menu: SeparatorMenu, id: Id) {
+        let ctx = ui.ctx().clone();
+        if menu.reset_size && ui.button("Reset size").clicked() {
+            ctx.data_mut(|d| d.remove::<PanelState>(id));
+            ui.close_menu();
+        }
+        if menu.collapse && ui.button("Collapse").clicked() {
+            self.set_expanded(&ctx, false);
+            ui.close_menu();
+        }
+        if menu.hide && ui.button("Hide").clicked() {
+            let mut state = DynamicPanelState::load(&ctx, self.id());
+            state.collapsed = true;
+            state.hidden = true;
+            state.auto_expanded = false;
+            state.store(&ctx, self.id());
+            ui.close_menu();
+        }
+        if menu.move_to_other_side && ui.button("Move to other side").clicked() {
+            let mut state = DynamicPanelState::load(&ctx, self.id());
+            state.flipped = !state.flipped;
+            state.store(&ctx, self.id());
+            ctx.data_mut(|d| d.remove::<PanelState>(id));
+            ui.close_menu();
+        }
+    }
+
+    /// `cfg` with the [`PanelDefaults`] filled in and the frame of [`Self::with_frame_fn`], on the opposite side if
+    /// the panel was moved there with the [separator menu](Self::separator_menu).
+    fn placed<'c>(&self, ctx: &Context, cfg: Cow<'c, SinglePanelCfg>) -> Cow<'c, SinglePanelCfg> {
+        let mut cfg = match cfg {
+            Cow::Borrowed(cfg) => cfg.with_defaults(ctx),
+            Cow::Owned(cfg) => Cow::Owned(cfg.with_defaults(ctx).into_owned()),
+        };
+        if let Some(frame) = self.dynamic_frame(ctx) {
+            cfg.to_mut().frame = Some(frame);
+        }
+        if !DynamicPanelState::load(ctx, self.id()).flipped {
+            return cfg;
+        }
+        Cow::Owned(cfg.mirrored())
+    }
+
+    /// Id of the size shared by the [`Self::size_group`] along the axis of `cfg`.
+    fn size_group_id(&self, cfg: &SinglePanelCfg) -> Option<Id> {
+        let axis = if cfg.side.is_lr() { "width" } else { "height" };
+        self.size_group
+            .map(|group| group.with("__dpanel_size_group").with(axis))
+    }
+
+    /// Resizes the panel to the size of its group before it is shown.
+    fn follow_size_group(&self, ctx: &Context, cfg: &SinglePanelCfg, id: Id) {
+        let Some(group_id) = self.size_group_id(cfg) else {
+            return;
+        };
+        let Some(size) = ctx.data(|d| d.get_temp::<f32>(group_id)) else {
+            return;
+        };
+        if let Some(PanelState { mut rect }) = PanelState::load(ctx, id) {
+            if cfg.side.rect_size(rect) != size {
+                cfg.side.set_rect_size(&mut rect, size);
+                ctx.data_mut(|d| d.insert_persisted(id, PanelState { rect }));
+            }
+        }
+    }
+
+    /// Makes the size the panel was shown at the size of its group while the user resizes it, or if the group has
+    /// no size yet. Only the resized panel leads, the others keep their clamped sizes to themselves.
+    fn lead_size_group(&self, ctx: &Context, cfg: &SinglePanelCfg, id: Id) {
+        let Some(group_id) = self.size_group_id(cfg) else {
+            return;
+        };
+        // The stored state, not the response: while animating, the response is that of a stand-in panel.
+        let Some(PanelState { rect }) = PanelState::load(ctx, id) else {
+            return;
+        };
+        let size = cfg.side.rect_size(rect);
+        let group_size = ctx.data(|d| d.get_temp::<f32>(group_id));
+        let resizing = ctx
+            .read_response(id.with("__resize"))
+            .is_some_and(|response| response.dragged())
+            || ctx.memory(|m| m.has_focus(id.with("__separator")));
+        if (resizing || group_size.is_none()) && group_size != Some(size) {
+            ctx.data_mut(|d| d.insert_temp(group_id, size));
+            ctx.request_repaint();
+        }
+    }
+
+    /// Content faded to `opacity`, used instead of sliding when motion is reduced.
+    fn faded<R>(opacity: f32, content: impl Fn(&mut Ui) -> R) -> impl Fn(&mut Ui) -> R {
+        move |ui| {
+            ui.multiply_opacity(opacity);
+            content(ui)
+        }
+    }
+
+    fn show_panel<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ctx: &'a Context,
+        content: F,
+        name: impl Into<egui::Id>,
+    ) -> egui::InnerResponse<R> {
+        let id = name.into();
+        let cfg = self.placed(ctx, cfg.sized_for(ctx.available_rect()));
+        let cfg = self.rubber_banded(ctx, cfg, id);
+        let cfg = self.snapped(ctx, cfg, id);
+        let cfg = &*cfg.at_screen_edge(ctx);
+        let style = ctx.style();
+        let content = |ui: &mut Ui| {
+            Self::unstyled_ctx(ui, &style, |ui| {
+                cfg.paint_background(ui);
+                content(ui)
+            })
+        };
+        self.with_ctx_style(cfg, ctx, || {
+            self.follow_size_group(ctx, cfg, id);
+            let response = match cfg.side {
+                Side::Left | Side::Right => {
+                    let panel = cfg.build_side_panel(id);
+                    panel.show(ctx, |ui| self.add_contents(ui, id, Some(cfg.side), content))
+                }
+                Side::Top | Side::Bottom => {
+                    let panel = cfg.build_top_bottom_panel(id);
+                    panel.show(ctx, |ui| self.add_contents(ui, id, Some(cfg.side), content))
+                }
+            };
+            self.separator_interaction_ctx(cfg, ctx, id, response.response.rect);
+            self.lead_size_group(ctx, cfg, id);
+            #[cfg(debug_assertions)]
+            collision::shown(ctx, id, &self.name);
+            self.end_show(ctx, Some(response.response.rect));
+            response
+        })
+    }
+
+    fn show_panel_inside<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ui: &mut Ui,
+        content: F,
+        name: impl Into<egui::Id>,
+    ) -> egui::InnerResponse<R> {
+        let id = name.into();
+        let cfg = self.placed(ui.ctx(), cfg.sized_for(ui.available_rect_before_wrap()));
+        let cfg = self.rubber_banded(ui.ctx(), cfg, id);
+        let cfg = &*self.snapped(ui.ctx(), cfg, id);
+        let style = ui.style().clone();
+        let content = |ui: &mut Ui| {
+            Self::unstyled(ui, &style, |ui| {
+                cfg.paint_background(ui);
+                content(ui)
+            })
+        };
+        self.with_ui_style(cfg, ui, |ui| {
+            self.follow_size_group(ui.ctx(), cfg, id);
+            let response = match cfg.side {
+                Side::Left | Side::Right => {
+                    let panel = cfg.build_side_panel(id);
+                    panel.show_inside(ui, |ui| self.add_contents(ui, id, Some(cfg.side), content))
+                }
+                Side::Top | Side::Bottom => {
+                    let panel = cfg.build_top_bottom_panel(id);
+                    panel.show_inside(ui, |ui| self.add_contents(ui, id, Some(cfg.side), content))
+                }
+            };
+            self.separator_interaction(cfg, ui, id, response.response.rect);
+            self.lead_size_group(ui.ctx(), cfg, id);
+            #[cfg(debug_assertions)]
+            collision::shown(ui.ctx(), id, &self.name);
+            self.end_show(ui.ctx(), Some(response.response.rect));
+            response
+        })
+    }
+
+    fn show_panel_animated<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ctx: &'a Context,
+        is_expanded: bool,
+        content: F,
+        name: impl Into<egui::Id>,
+    ) -> Option<egui::InnerResponse<R>> {
+        let id = name.into();
+        if self.reduces_motion(ctx) {
+            let opacity = ctx.animate_bool_responsive(id.with("animation"), is_expanded);
+            if opacity == 0.0 {
+                self.end_show(ctx, None);
+                return None;
+            }
+            return Some(self.show_panel(cfg, ctx, Self::faded(opacity, content), id));
+        }
+        let cfg = self.placed(ctx, cfg.sized_for(ctx.available_rect()));
+        let cfg = self.rubber_banded(ctx, cfg, id);
+        let cfg = self.snapped(ctx, cfg, id);
+        let cfg = &*cfg.at_screen_edge(ctx);
+        let style = ctx.style();
+        let content = |ui: &mut Ui| {
+            Self::unstyled_ctx(ui, &style, |ui| {
+                cfg.paint_background(ui);
+                content(ui)
+            })
+        };
+        self.with_ctx_style(cfg, ctx, || {
+            self.follow_size_group(ctx, cfg, id);
+            let response = match cfg.side {
+                Side::Left | Side::Right => {
+                    let panel = cfg.build_side_panel(id);
+                    panel.show_animated(ctx, is_expanded, |ui| {
+                        self.add_contents(ui, id, Some(cfg.side), content)
+                    })
+                }
+                Side::Top | Side::Bottom => {
+                    let panel = cfg.build_top_bottom_panel(id);
+                    panel.show_animated(ctx, is_expanded, |ui| {
+                        self.add_contents(ui, id, Some(cfg.side), content)
+                    })
+                }
+            };
+            if let Some(response) = &response {
+                self.separator_interaction_ctx(cfg, ctx, id, response.response.rect);
+                #[cfg(debug_assertions)]
+                collision::shown(ctx, id, &self.name);
+            }
+            self.lead_size_group(ctx, cfg, id);
+            self.end_show(ctx, response.as_ref().map(|r| r.response.rect));
+            response
+        })
+    }
+
+    fn show_panel_animated_inside<R, F: Fn(&mut egui::Ui) -> R>(
+        &self,
+        cfg: &SinglePanelCfg,
+        ui: &mut Ui,
+        is_expanded: bool,
+        content: F,
+        name: impl Into<egui::Id>,
+    ) -> Option<egui::InnerResponse<R>> {
+        let id = name.into();
+        if self.reduces_motion(ui.ctx()) {
+            let opacity = ui
+                .ctx()
+                .animate_bool_responsive(id.with("animation"), is_expanded);
+            if opacity == 0.0 {
+                self.end_show(ui.ctx(), None);
+                return None;
+            }
+            return Some(self.show_panel_inside(cfg, ui, Self::faded(opacity, content), id));
+        }
+        let cfg = self.placed(ui.ctx(), cfg.sized_for(ui.available_rect_before_wrap()));
+        let cfg = self.rubber_banded(ui.ctx(), cfg, id);
+        let cfg = &*self.snapped(ui.ctx(), cfg, id);
+        let style = ui.style().clone();
+        let content = |ui: &mut Ui| {
+            Self::unstyled(ui, &style, |ui| {
+                cfg.paint_background(ui);
+                content(ui)
+            })
+        };
+        self.with_ui_style(cfg, ui, |ui| {
+            self.follow_size_group(ui.ctx(), cfg, id);
+            let response = match cfg.side {
+                Side::Left | Side::Right => {
+                    let panel = cfg.build_side_panel(id);
+                    panel.show_animated_inside(ui, is_expanded, |ui| {
+                        self.add_contents(ui, id, Some(cfg.side), content)
+                    })
+                }
+                Side::Top | Side::Bottom => {
+                    let panel = cfg.build_top_bottom_panel(id);
+                    panel.show_animated_inside(ui, is_expanded, |ui| {
+                        self.add_contents(ui, id, Some(cfg.side), content)
+                    })
+                }
+            };
+            if let Some(response) = &response {
+                self.separator_interaction(cfg, ui, id, response.response.rect);
+                #[cfg(debug_assertions)]
+                collision::shown(ui.ctx(), id, &self.name);
+            }
+            self.lead_size_group(ui.ctx(), cfg, id);
+            self.end_show(ui.ctx(), response.as_ref().map(|r| r.response.rect));
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::LayoutProbe;
+
+    /// Runs a frame of `app` at `time` seconds on a screen of 800x600 points, with input `events`.
+    fn frame(
+        ctx: &Context,
+        time: f64,
+        events: Vec<egui::Event>,
+        app: impl FnMut(&Context),
+    ) -> egui::FullOutput {
+        let input = egui::RawInput {
+            screen_rect: Some(Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(800.0, 600.0),
+            )),
+            time: Some(time),
+            events,
+            ..Default::default()
+        };
+        ctx.run(input, app)
+    }
+
+    /// What `panel` showed dynamically on a screen of `size` points, once it settled.
+    fn shown(
+        panel: fn(&Context) -> DynamicPanel<'_>,
+        size: egui::Vec2,
+    ) -> Option<testing::ShownPanel> {
+        let mut probe = LayoutProbe::new();
+        probe.run(size, |ctx| {
+            panel(ctx).show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        let shown = probe.shown(&panel(probe.context()));
+        shown
+    }
+
+    /// Rect of a panel showing only `cfg` on a screen of 800x600 points.
+    fn shown_rect(cfg: SinglePanelCfg) -> Rect {
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            DynamicPanel::new("panel")
+                .with_panels([cfg.clone().into()])
+                .with_choice_function(|_| 0)
+                .show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.shown(&DynamicPanel::new("panel")).unwrap().rect
+    }
+
+    /// Whether the left panel `panel` can be resized with the pointer `distance` points right of its edge.
+    fn resizable_from(panel: fn(&Context) -> DynamicPanel<'_>, distance: f32) -> bool {
+        let ctx = Context::default();
+        let app = |ctx: &Context| {
+            panel(ctx).show_dynamic(ctx, |ui| ui.label("…"));
+        };
+        // egui only senses the separator once the panel was laid out in a previous frame.
+        frame(&ctx, 0.0, vec![], app);
+        frame(&ctx, 0.05, vec![], app);
+        let edge = PanelState::load(&ctx, panel(&ctx).id())
+            .unwrap()
+            .rect
+            .right();
+        let pointer = egui::Event::PointerMoved(egui::pos2(edge + distance, 300.0));
+        let output = frame(&ctx, 0.1, vec![pointer], app);
+        output.platform_output.cursor_icon != egui::CursorIcon::Default
+    }
+
+    /// Config index a test picked, for choice functions.
+    #[cfg(feature = "animation")]
+    fn chosen(ctx: &Context) -> usize {
+        ctx.data(|d| d.get_temp(Id::new("chosen"))).unwrap_or(0)
+    }
+
+    /// A frame of a panel switching configs.
+    #[cfg(feature = "animation")]
+    #[derive(Debug)]
+    struct Sample {
+        /// See [`DynamicPanelState::transition_progress`].
+        progress: Option<f32>,
+        /// Opacity and width of the content, `None` if it wasn't shown.
+        content: Option<(f32, f32)>,
+    }
+
+    /// Shows `panel` on config 0 until it settled, then has [`chosen`] pick config 1 and samples the frames after,
+    /// 10 ms apart.
+    #[cfg(feature = "animation")]
+    fn switch(panel: fn(&Context) -> DynamicPanel<'_>) -> Vec<Sample> {
+        let ctx = Context::default();
+        let content = std::cell::Cell::new(None);
+        let app = |ctx: &Context| {
+            content.set(None);
+            panel(ctx).show_dynamic(ctx, |ui| {
+                content.set(Some((ui.opacity(), ui.max_rect().width())));
+            });
+        };
+        frame(&ctx, 0.0, vec![], app);
+        frame(&ctx, 1.0, vec![], app);
+        ctx.data_mut(|d| d.insert_temp(Id::new("chosen"), 1_usize));
+        (0..20)
+            .map(|i| {
+                frame(&ctx, 2.0 + i as f64 * 0.01, vec![], app);
+                Sample {
+                    progress: panel(&ctx).state(&ctx).transition_progress(&ctx),
+                    content: content.get(),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a widget outside of `panel` keeps the focus it was given, on a screen `width` points wide.
+    fn focus_stays_outside(panel: fn(&Context) -> DynamicPanel<'_>, width: f32) -> bool {
+        let outside = Id::new("outside");
+        let app = |ctx: &Context| {
+            panel(ctx).show_dynamic_collapsible(ctx, |ui| ui.button("inside"));
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let corner = Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(10.0, 10.0));
+                ui.interact(corner, outside, Sense::click());
+            });
+        };
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(width, 600.0), app);
+        probe.context().memory_mut(|m| m.request_focus(outside));
+        probe.run(egui::vec2(width, 600.0), app);
+        probe.context().memory(|m| m.focused()) == Some(outside)
+    }
+
+    #[test]
+    fn side_panels_are_mapped_last() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            let cfg = SinglePanelCfg::left()
+                .exact_size(100.0)
+                .map_side_panel(|panel| panel.exact_width(222.0));
+            DynamicPanel::new("sidebar")
+                .with_panels([cfg.into()])
+                .with_choice_function(|_| 0)
+        }
+        let rect = shown(sidebar, egui::vec2(800.0, 600.0)).unwrap().rect;
+        assert_eq!(rect.width(), 222.0);
+    }
+
+    #[test]
+    fn overlays_are_anchored_to_the_screen() {
+        fn popup(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("popup")
+                .with_panels([OverlayPanelCfg::new(
+                    egui::Align2::RIGHT_BOTTOM,
+                    egui::vec2(-10.0, -20.0),
+                )
+                .into()])
+                .with_choice_function(|_| 0)
+        }
+        let rect = shown(popup, egui::vec2(800.0, 600.0)).unwrap().rect;
+        assert_eq!(rect.right_bottom(), egui::pos2(790.0, 580.0));
+    }
+
+    #[test]
+    fn status_bars_turn_into_toasts_that_hide() {
+        fn status(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::status_bar("status", 400.0)
+        }
+        let tall = shown(status, egui::vec2(800.0, 600.0)).unwrap();
+        assert_eq!(tall.index, 0);
+        let mut probe = LayoutProbe::new().frames(1);
+        let app = |ctx: &Context| {
+            status(ctx).show_dynamic(ctx, |ui| ui.label("…"));
+        };
+        probe.run(egui::vec2(800.0, 300.0), app);
+        probe.assert_index(&status(probe.context()), 1);
+        for _ in 0..4 {
+            probe.run(egui::vec2(800.0, 300.0), app);
+        }
+        assert_eq!(probe.shown(&status(probe.context())), None);
+        status(probe.context()).reveal(probe.context());
+        probe.run(egui::vec2(800.0, 300.0), app);
+        probe.assert_index(&status(probe.context()), 1);
+    }
+
+    #[test]
+    fn switching_configs_on_resize_repaints() {
+        let ctx = Context::default();
+        let repaint_delay = |width: f32| {
+            let input = egui::RawInput {
+                screen_rect: Some(Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(width, 600.0),
+                )),
+                ..Default::default()
+            };
+            let output = ctx.run(input, |ctx| {
+                DynamicPanel::new("sidebar")
+                    .with_panels([SinglePanelCfg::left().into(), SinglePanelCfg::top().into()])
+                    .with_rect_choice_function(|rect| usize::from(rect.width() < 600.0))
+                    .show_dynamic(ctx, |ui| ui.label("…"));
+            });
+            output.viewport_output[&egui::ViewportId::ROOT].repaint_delay
+        };
+        repaint_delay(800.0);
+        repaint_delay(800.0);
+        assert_ne!(repaint_delay(700.0), std::time::Duration::ZERO);
+        assert_eq!(repaint_delay(400.0), std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn safe_area_insets_pad_the_edges_the_panel_touches() {
+        let content = |safe_area: SafeArea| {
+            let rect = std::cell::Cell::new(Rect::NOTHING);
+            frame(&Context::default(), 0.0, vec![], |ctx| {
+                set_safe_area_insets(
+                    ctx,
+                    Margin {
+                        left: 30.0,
+                        right: 40.0,
+                        top: 10.0,
+                        bottom: 0.0,
+                    },
+                );
+                DynamicPanel::new("sidebar")
+                    .with_panels([SinglePanelCfg::left().safe_area(safe_area).into()])
+                    .show(ctx, 0, |ui| rect.set(ui.max_rect()));
+            });
+            rect.get()
+        };
+        let ignored = content(SafeArea::Ignore);
+        let padded = content(SafeArea::Integration);
+        assert_eq!(padded.left() - ignored.left(), 30.0);
+        assert_eq!(padded.top() - ignored.top(), 10.0);
+        let custom = content(SafeArea::Custom(Margin::same(5.0)));
+        assert_eq!(custom.left() - ignored.left(), 5.0);
+    }
+
+    #[cfg(feature = "egui_extras")]
+    #[test]
+    fn relative_sizes_are_a_fraction_of_the_screen() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left()
+                    .size(egui_extras::Size::relative(0.25))
+                    .into()])
+                .with_choice_function(|_| 0)
+        }
+        let rect = shown(sidebar, egui::vec2(800.0, 600.0)).unwrap().rect;
+        assert_eq!(rect.width(), 200.0);
+        let rect = shown(sidebar, egui::vec2(400.0, 600.0)).unwrap().rect;
+        assert_eq!(rect.width(), 100.0);
+    }
+
+    #[test]
+    fn detached_panels_leave_the_layout() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().exact_size(200.0).into()])
+                .with_choice_function(|_| 0)
+        }
+        let mut probe = LayoutProbe::new();
+        let central = std::cell::Cell::new(Rect::NOTHING);
+        let central_width = |probe: &mut LayoutProbe| {
+            probe.run(egui::vec2(800.0, 600.0), |ctx| {
+                sidebar(ctx).show_dynamic(ctx, |ui| ui.label("…"));
+                egui::CentralPanel::default().show(ctx, |ui| central.set(ui.max_rect()));
+            });
+            central.get().width()
+        };
+        assert!(central_width(&mut probe) < 600.0);
+        sidebar(probe.context()).detach(probe.context());
+        assert!(sidebar(probe.context()).is_detached(probe.context()));
+        assert!(central_width(&mut probe) > 780.0);
+        sidebar(probe.context()).dock(probe.context());
+        assert!(central_width(&mut probe) < 600.0);
+    }
+
+    #[test]
+    fn action_bars_face_the_central_area() {
+        let rects = |cfg: SinglePanelCfg| {
+            let content = std::cell::Cell::new(Rect::NOTHING);
+            let ctx = Context::default();
+            frame(&ctx, 0.0, vec![], |ctx| {
+                DynamicPanel::new("panel")
+                    .with_panels([cfg.clone().into()])
+                    .action_bar(40.0, |ui| {
+                        let rect = ui.max_rect();
+                        ui.data_mut(|d| d.insert_temp(Id::new("bar"), rect));
+                    })
+                    .show(ctx, 0, |ui| content.set(ui.max_rect()));
+            });
+            let bar: Rect = ctx.data(|d| d.get_temp(Id::new("bar"))).unwrap();
+            (content.get(), bar)
+        };
+        let (content, bar) = rects(SinglePanelCfg::left());
+        assert_eq!(bar.height(), 40.0);
+        assert_eq!(content.bottom(), bar.top());
+        let (content, bar) = rects(SinglePanelCfg::bottom().exact_size(200.0));
+        assert_eq!(bar.height(), 40.0);
+        assert_eq!(bar.bottom(), content.top());
+    }
+
+    /// A click at `pos` in a frame at `time`, as the press and release events of two frames.
+    fn click(ctx: &Context, time: f64, pos: egui::Pos2, mut app: impl FnMut(&Context)) {
+        let press = |pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        frame(
+            ctx,
+            time,
+            vec![egui::Event::PointerMoved(pos), press(true)],
+            &mut app,
+        );
+        frame(ctx, time + 0.05, vec![press(false)], &mut app);
+    }
+
+    #[test]
+    fn clicking_the_separator_collapses_the_panel() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            let cfg = CollapsiblePanelCfg::new(
+                SinglePanelCfg::left().exact_size(40.0),
+                SinglePanelCfg::left(),
+            );
+            DynamicPanel::new("sidebar")
+                .with_panels([PanelCfg::Collapsible(cfg)])
+                .with_choice_function(|_| 0)
+                .collapse_on_separator_click(true)
+        }
+        let ctx = Context::default();
+        let app = |ctx: &Context| {
+            sidebar(ctx).show_dynamic_collapsible(ctx, |ui| ui.label("…"));
+        };
+        frame(&ctx, 0.0, vec![], app);
+        frame(&ctx, 1.0, vec![], app);
+        assert!(sidebar(&ctx).is_expanded(&ctx));
+        let edge = PanelState::load(&ctx, sidebar(&ctx).panel_id(&ctx))
+            .unwrap()
+            .rect
+            .right();
+        click(&ctx, 2.0, egui::pos2(edge, 300.0), app);
+        assert!(!sidebar(&ctx).is_expanded(&ctx));
+    }
+
+    #[test]
+    fn rubber_bands_stretch_past_the_limit_and_spring_back() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().size_range(100.0, 200.0).into()])
+                .with_choice_function(|_| 0)
+                .rubber_band(true)
+        }
+        let ctx = Context::default();
+        let app = |ctx: &Context| {
+            sidebar(ctx).show_dynamic(ctx, |ui| ui.allocate_space(ui.available_size()));
+        };
+        let width = |ctx: &Context| {
+            PanelState::load(ctx, sidebar(ctx).panel_id(ctx))
+                .unwrap()
+                .rect
+                .width()
+        };
+        frame(&ctx, 0.0, vec![], app);
+        frame(&ctx, 1.0, vec![], app);
+        let edge = egui::pos2(width(&ctx), 300.0);
+        let outside = egui::pos2(400.0, 300.0);
+        let press = |pos, pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        frame(&ctx, 2.0, vec![egui::Event::PointerMoved(edge)], app);
+        frame(&ctx, 2.1, vec![press(edge, true)], app);
+        for i in 0..3 {
+            let pointer = egui::Event::PointerMoved(outside);
+            frame(&ctx, 2.2 + f64::from(i) * 0.1, vec![pointer], app);
+        }
+        let stretched = width(&ctx);
+        assert!(stretched > 200.0 && stretched < 200.0 + RUBBER_BAND_DISTANCE);
+        frame(&ctx, 3.0, vec![press(outside, false)], app);
+        for i in 0..3 {
+            frame(&ctx, 4.0 + f64::from(i), vec![], app);
+        }
+        assert_eq!(width(&ctx), 200.0);
+    }
+
+    #[test]
+    fn separator_menu_moves_the_panel_to_the_other_side() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_choice_function(|_| 0)
+                .separator_menu(SeparatorMenu {
+                    reset_size: false,
+                    collapse: false,
+                    hide: false,
+                    move_to_other_side: true,
+                })
+        }
+        let ctx = Context::default();
+        let app = |ctx: &Context| {
+            sidebar(ctx).show_dynamic(ctx, |ui| ui.label("…"));
+        };
+        let rect = |ctx: &Context| {
+            PanelState::load(ctx, sidebar(ctx).panel_id(ctx))
+                .unwrap()
+                .rect
+        };
+        frame(&ctx, 0.0, vec![], app);
+        frame(&ctx, 1.0, vec![], app);
+        let edge = egui::pos2(rect(&ctx).right(), 300.0);
+        let press = |pressed| egui::Event::PointerButton {
+            pos: edge,
+            button: egui::PointerButton::Secondary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        frame(
+            &ctx,
+            2.0,
+            vec![egui::Event::PointerMoved(edge), press(true)],
+            app,
+        );
+        frame(&ctx, 2.1, vec![press(false)], app);
+        frame(&ctx, 2.2, vec![], app);
+        // The only entry of the menu, which opens at the pointer.
+        click(&ctx, 3.0, edge + egui::vec2(30.0, 15.0), app);
+        frame(&ctx, 4.0, vec![], app);
+        frame(&ctx, 5.0, vec![], app);
+        assert_eq!(rect(&ctx).right(), 800.0);
+    }
+
+    #[test]
+    fn size_setters_apply_to_the_axis_of_the_side() {
+        let rect = shown_rect(SinglePanelCfg::right().exact_size(250.0));
+        assert_eq!((rect.width(), rect.right()), (250.0, 800.0));
+        let rect = shown_rect(SinglePanelCfg::bottom().exact_size(80.0));
+        assert_eq!((rect.height(), rect.bottom()), (80.0, 600.0));
+        let rect = shown_rect(SinglePanelCfg::top().size_range(100.0, 120.0));
+        assert_eq!(rect.height(), 100.0);
+    }
+
+    #[test]
+    fn mirrored_configs_dock_on_the_opposite_side() {
+        let frame = Frame::default().inner_margin(Margin {
+            left: 30.0,
+            ..Default::default()
+        });
+        let mut cfg = SinglePanelCfg::left().exact_size(250.0);
+        cfg.frame = Some(frame);
+        let mirrored = cfg.mirrored();
+        assert_eq!(mirrored.frame.unwrap().inner_margin.right, 30.0);
+        let rect = shown_rect(mirrored);
+        assert_eq!((rect.width(), rect.right()), (250.0, 800.0));
+    }
+
+    #[test]
+    fn configs_moved_to_another_axis_swap_their_sizes() {
+        let sidebar = SinglePanelCfg::left().exact_size(200.0);
+        let rect = shown_rect(sidebar.to_bottom());
+        assert_eq!((rect.height(), rect.bottom()), (200.0, 600.0));
+        let rect = shown_rect(sidebar.to_right());
+        assert_eq!((rect.width(), rect.right()), (200.0, 800.0));
+    }
+
+    #[test]
+    fn scaled_configs_scale_their_sizes() {
+        let rect = shown_rect(SinglePanelCfg::left().exact_size(100.0).scaled(1.5));
+        assert_eq!(rect.width(), 150.0);
+        let rect = shown_rect(SinglePanelCfg::top().size_range(40.0, 60.0).scaled(2.0));
+        assert_eq!(rect.height(), 80.0);
+    }
+
+    #[test]
+    fn panels_inherit_the_defaults_they_dont_set() {
+        let content_left = |cfg: SinglePanelCfg| {
+            let left = std::cell::Cell::new(f32::NAN);
+            frame(&Context::default(), 0.0, vec![], |ctx| {
+                set_panel_defaults(
+                    ctx,
+                    PanelDefaults {
+                        frame: Some(Frame::default().inner_margin(25.0)),
+                        ..Default::default()
+                    },
+                );
+                DynamicPanel::new("sidebar")
+                    .with_panels([cfg.clone().into()])
+                    .show(ctx, 0, |ui| left.set(ui.max_rect().left()));
+            });
+            left.get()
+        };
+        assert_eq!(content_left(SinglePanelCfg::left()), 25.0);
+        let mut cfg = SinglePanelCfg::left();
+        cfg.frame = Some(Frame::default().inner_margin(5.0));
+        assert_eq!(content_left(cfg), 5.0);
+    }
+
+    #[test]
+    fn frame_functions_run_every_frame() {
+        let ctx = Context::default();
+        let content_left = |margin: f32| {
+            let left = std::cell::Cell::new(f32::NAN);
+            ctx.data_mut(|d| d.insert_temp(Id::new("margin"), margin));
+            frame(&ctx, 0.0, vec![], |ctx| {
+                let mut cfg = SinglePanelCfg::left();
+                cfg.frame = Some(Frame::default().inner_margin(5.0));
+                DynamicPanel::new("sidebar")
+                    .with_panels([cfg.into()])
+                    .with_frame_fn(|ctx, _| {
+                        let margin: f32 = ctx.data(|d| d.get_temp(Id::new("margin")).unwrap());
+                        Frame::default().inner_margin(margin)
+                    })
+                    .show(ctx, 0, |ui| left.set(ui.max_rect().left()));
+            });
+            left.get()
+        };
+        assert_eq!(content_left(10.0), 10.0);
+        assert_eq!(content_left(20.0), 20.0);
+    }
+
+    #[test]
+    fn configs_are_chosen_for_the_previewed_screen() {
+        fn sidebar<'a>() -> DynamicPanel<'a> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into(), SinglePanelCfg::top().into()])
+                .with_choice_function(|ctx| usize::from(screen_rect(ctx).width() < 600.0))
+        }
+        fn previewed(ctx: &Context) -> DynamicPanel<'_> {
+            let preview = ScreenPreview {
+                size: egui::vec2(400.0, 800.0),
+                show_boundary: true,
+            };
+            set_screen_preview(ctx, Some(preview));
+            sidebar()
+        }
+        fn unpreviewed(ctx: &Context) -> DynamicPanel<'_> {
+            set_screen_preview(ctx, None);
+            sidebar()
+        }
+        let size = egui::vec2(800.0, 600.0);
+        assert_eq!(shown(previewed, size).unwrap().index, 1);
+        assert_eq!(shown(unpreviewed, size).unwrap().index, 0);
+    }
+
+    #[test]
+    fn zoom_aware_breakpoints_ignore_the_zoom() {
+        fn sidebar<'a>() -> DynamicPanel<'a> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into(), SinglePanelCfg::top().into()])
+                .with_choice_function(|ctx| usize::from(breakpoint_rect(ctx).width() < 600.0))
+        }
+        fn zoomed(ctx: &Context) -> DynamicPanel<'_> {
+            ctx.set_zoom_factor(2.0);
+            sidebar()
+        }
+        fn zoom_aware(ctx: &Context) -> DynamicPanel<'_> {
+            set_zoom_aware_breakpoints(ctx, true);
+            zoomed(ctx)
+        }
+        // A window 1000 points wide before the zoom.
+        let size = egui::vec2(500.0, 300.0);
+        assert_eq!(shown(zoomed, size).unwrap().index, 1);
+        assert_eq!(shown(zoom_aware, size).unwrap().index, 0);
+    }
+
+    #[test]
+    fn dragging_a_payload_to_the_edge_expands_the_panel() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_choice_function(|_| 0)
+                .expand_on_drag(30.0)
+        }
+        let ctx = Context::default();
+        let dragging = std::cell::Cell::new(false);
+        let app = |ctx: &Context| {
+            if dragging.get() {
+                egui::DragAndDrop::set_payload(ctx, "file");
+            }
+            sidebar(ctx).show_dynamic_collapsible(ctx, |ui| ui.label("…"));
+        };
+        frame(&ctx, 0.0, vec![], app);
+        sidebar(&ctx).set_expanded(&ctx, false);
+        frame(&ctx, 1.0, vec![], app);
+        dragging.set(true);
+        let far = egui::Event::PointerMoved(egui::pos2(400.0, 300.0));
+        frame(&ctx, 2.0, vec![far.clone()], app);
+        frame(&ctx, 2.1, vec![], app);
+        assert!(!sidebar(&ctx).is_expanded(&ctx));
+        let near = egui::Event::PointerMoved(egui::pos2(20.0, 300.0));
+        frame(&ctx, 3.0, vec![near], app);
+        frame(&ctx, 3.1, vec![], app);
+        assert!(sidebar(&ctx).is_expanded(&ctx));
+        // Dropped elsewhere, so the panel collapses again.
+        dragging.set(false);
+        let drop = egui::Event::PointerButton {
+            pos: egui::pos2(400.0, 300.0),
+            button: egui::PointerButton::Primary,
+            pressed: false,
+            modifiers: Default::default(),
+        };
+        frame(&ctx, 4.0, vec![far, drop], app);
+        frame(&ctx, 4.1, vec![], app);
+        assert!(!sidebar(&ctx).is_expanded(&ctx));
+    }
+
+    #[test]
+    fn adapted_content_flows_along_the_side() {
+        let direction = |cfg: SinglePanelCfg, adapt: bool| {
+            let direction = std::cell::Cell::new(None);
+            frame(&Context::default(), 0.0, vec![], |ctx| {
+                DynamicPanel::new("panel")
+                    .with_panels([cfg.clone().into()])
+                    .adapt_layout(adapt)
+                    .show(ctx, 0, |ui| direction.set(Some(ui.layout().main_dir())));
+            });
+            direction.get().unwrap()
+        };
+        assert_eq!(
+            direction(SinglePanelCfg::top(), true),
+            egui::Direction::LeftToRight
+        );
+        assert_eq!(
+            direction(SinglePanelCfg::left(), true),
+            egui::Direction::TopDown
+        );
+        assert_eq!(
+            direction(SinglePanelCfg::top(), false),
+            egui::Direction::TopDown
+        );
+    }
+
+    #[test]
+    fn egui_panels_are_only_built_for_their_axis() {
+        let sidebar = SinglePanelCfg::left().exact_size(120.0);
+        assert!(sidebar.top_bottom_panel("sidebar").is_none());
+        assert!(SinglePanelCfg::bottom().side_panel("bar").is_none());
+        let rect = std::cell::Cell::new(Rect::NOTHING);
+        frame(&Context::default(), 0.0, vec![], |ctx| {
+            let panel = sidebar.side_panel("sidebar").unwrap();
+            rect.set(panel.show(ctx, |ui| ui.label("…")).response.rect);
+        });
+        assert_eq!(rect.get().width(), 120.0);
+    }
+
+    #[test]
+    fn dragged_separators_snap_to_the_sizes() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().size_range(100.0, 400.0).into()])
+                .with_choice_function(|_| 0)
+                .resize_snap(ResizeSnap::Sizes(vec![150.0, 250.0, 350.0]))
+        }
+        let ctx = Context::default();
+        let app = |ctx: &Context| {
+            sidebar(ctx).show_dynamic(ctx, |ui| ui.allocate_space(ui.available_size()));
+        };
+        let width = |ctx: &Context| {
+            PanelState::load(ctx, sidebar(ctx).panel_id(ctx))
+                .unwrap()
+                .rect
+                .width()
+        };
+        frame(&ctx, 0.0, vec![], app);
+        frame(&ctx, 1.0, vec![], app);
+        let edge = egui::pos2(width(&ctx), 300.0);
+        let to = egui::pos2(270.0, 300.0);
+        let press = |pos, pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        frame(&ctx, 2.0, vec![egui::Event::PointerMoved(edge)], app);
+        frame(&ctx, 2.1, vec![press(edge, true)], app);
+        frame(&ctx, 2.2, vec![egui::Event::PointerMoved(to)], app);
+        frame(&ctx, 2.3, vec![], app);
+        assert_eq!(width(&ctx), 250.0);
+        frame(&ctx, 2.4, vec![press(to, false)], app);
+        frame(&ctx, 3.0, vec![], app);
+        assert_eq!(width(&ctx), 250.0);
+    }
+
+    #[test]
+    fn overlays_are_anchored_to_the_constrained_region() {
+        fn popup(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("popup")
+                .with_panels([
+                    OverlayPanelCfg::new(egui::Align2::RIGHT_BOTTOM, egui::Vec2::ZERO).into(),
+                ])
+                .with_choice_function(|_| 0)
+                .constrain_to(Rect::from_min_max(
+                    egui::pos2(0.0, 50.0),
+                    egui::pos2(600.0, 500.0),
+                ))
+        }
+        let rect = shown(popup, egui::vec2(800.0, 600.0)).unwrap().rect;
+        assert_eq!(rect.right_bottom(), egui::pos2(600.0, 500.0));
+    }
+
+    #[test]
+    fn configs_wait_for_the_real_screen_size() {
+        let first_frame = |initial_size: Option<egui::Vec2>| {
+            let ctx = Context::default();
+            let mut index = None;
+            // No screen rect, like integrations that report it only after the first frame.
+            let _ = ctx.run(egui::RawInput::default(), |ctx| {
+                if let Some(size) = initial_size {
+                    set_initial_screen_size(ctx, size);
+                }
+                let sidebar = DynamicPanel::new("sidebar")
+                    .with_panels([SinglePanelCfg::left().into(), SinglePanelCfg::top().into()])
+                    .with_choice_function(|ctx| usize::from(screen_rect(ctx).width() < 600.0));
+                sidebar.show_dynamic(ctx, |ui| ui.label("…"));
+                index = sidebar.current_index(ctx);
+            });
+            index
+        };
+        assert_eq!(first_frame(None), None);
+        assert_eq!(first_frame(Some(egui::vec2(400.0, 800.0))), Some(1));
+    }
+
+    /// Whether the sidebar is expanded after each of two clicks on `button`, shown in the central panel.
+    fn expanded_after_clicks(
+        button: fn(&DynamicPanel<'static>, &mut Ui) -> egui::Response,
+    ) -> Vec<bool> {
+        fn sidebar<'a>() -> DynamicPanel<'a> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_choice_function(|_| 0)
+        }
+        let ctx = Context::default();
+        let rect = std::cell::Cell::new(Rect::NOTHING);
+        let app = |ctx: &Context| {
+            sidebar().show_dynamic_collapsible(ctx, |ui| ui.label("…"));
+            egui::CentralPanel::default().show(ctx, |ui| rect.set(button(&sidebar(), ui).rect));
+        };
+        frame(&ctx, 0.0, vec![], app);
+        (0..2)
+            .map(|i| {
+                click(&ctx, 1.0 + f64::from(i), rect.get().center(), app);
+                sidebar().is_expanded(&ctx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_collapse_button_toggles_the_panel() {
+        assert_eq!(
+            expanded_after_clicks(DynamicPanel::collapse_button),
+            [false, true]
+        );
+    }
+
+    #[test]
+    fn the_title_button_toggles_the_panel() {
+        fn title_button(panel: &DynamicPanel<'static>, ui: &mut Ui) -> egui::Response {
+            panel.title_button(ui, "Sidebar")
+        }
+        assert_eq!(expanded_after_clicks(title_button), [false, true]);
+    }
+
+    #[test]
+    fn modal_overlays_keep_the_focus() {
+        fn dialog(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("dialog")
+                .with_panels([
+                    OverlayPanelCfg::new(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO).into(),
+                ])
+                .with_choice_function(|_| 0)
+                .modal(true)
+        }
+        assert!(!focus_stays_outside(dialog, 400.0));
+    }
+
+    #[test]
+    fn dialog_preset_only_keeps_the_focus_as_a_dialog() {
+        fn dialog(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::dialog("settings", "Settings", 600.0)
+        }
+        assert!(focus_stays_outside(dialog, 1000.0));
+        assert!(!focus_stays_outside(dialog, 400.0));
+    }
+
+    #[test]
+    fn fab_sheet_preset_only_keeps_the_focus_as_a_sheet() {
+        fn sheet(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::fab_sheet("navigation", "☰", 600.0)
+        }
+        assert!(focus_stays_outside(sheet, 1000.0));
+    }
+
+    #[test]
+    fn collapsing_to_a_strip_is_reported_once() {
+        #[derive(Default)]
+        struct Count(std::sync::Mutex<Vec<&'static str>>);
+        impl LayoutObserver for Count {
+            fn on_index_changed(&self, _: &str, _: Option<usize>, _: usize) {
+                self.0.lock().unwrap().push("index");
+            }
+            fn on_collapse(&self, _: &str, _: bool) {
+                self.0.lock().unwrap().push("collapse");
+            }
+            fn on_dock_moved(&self, _: &str, _: Option<Side>) {
+                self.0.lock().unwrap().push("dock");
+            }
+        }
+        let count = Arc::new(Count::default());
+        fn panel<'a>(count: &Arc<Count>) -> DynamicPanel<'a> {
+            DynamicPanel::new("observed")
+                .with_panels([PanelCfg::Collapsible(CollapsiblePanelCfg::new(
+                    SideCfg::left().exact_width(32.0).resizable(false).into(),
+                    SideCfg::left().into(),
+                ))])
+                .with_choice_function(|_| 0)
+                .observer(count.clone())
+        }
+        let mut probe = LayoutProbe::new().frames(2);
+        let app = |ctx: &Context| {
+            panel(&count).show_dynamic_collapsible(ctx, |ui| ui.label("content"));
+        };
+        probe.run(egui::vec2(800.0, 600.0), app);
+        count.0.lock().unwrap().clear();
+        panel(&count).set_expanded(probe.context(), false);
+        probe.run(egui::vec2(800.0, 600.0), app);
+        assert_eq!(*count.0.lock().unwrap(), ["collapse"]);
+    }
+
+    #[test]
+    fn viewports_keep_their_own_size() {
+        let ctx = Context::default();
+        let other = egui::ViewportId::from_hash_of("other");
+        let app = |ctx: &Context| {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().exact_size(200.0).into(),
+                    SinglePanelCfg::left().exact_size(300.0).into(),
+                ])
+                .with_choice_function(|ctx| {
+                    usize::from(ctx.viewport_id() != egui::ViewportId::ROOT)
+                })
+                .show_dynamic(ctx, |ui| ui.label("…"));
+        };
+        for viewport_id in [egui::ViewportId::ROOT, other, egui::ViewportId::ROOT, other] {
+            let input = egui::RawInput {
+                viewport_id,
+                viewports: std::iter::once((viewport_id, Default::default())).collect(),
+                screen_rect: Some(Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(800.0, 600.0),
+                )),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, app);
+        }
+        let width = |id| PanelState::load(&ctx, id).map(|state| state.rect.width());
+        let id = Id::new("sidebar");
+        assert_eq!(width(id), Some(200.0));
+        assert_eq!(width(id.with(other)), Some(300.0));
+    }
+
+    #[test]
+    fn compact_content_returns_the_inner_value() {
+        let inner = |width: f32| {
+            let mut inner = None;
+            LayoutProbe::new().run(egui::vec2(800.0, 600.0), |ctx| {
+                let panel = DynamicPanel::new("sidebar")
+                    .with_panels([SinglePanelCfg::left().exact_size(width).into()])
+                    .with_choice_function(|_| 0);
+                let content = panel.compact_content(48.0, |_| "compact", |_| "full");
+                inner = panel
+                    .show_dynamic(ctx, content)
+                    .map(|response| response.inner);
+            });
+            inner
+        };
+        assert_eq!(inner(30.0), Some("compact"));
+        assert_eq!(inner(200.0), Some("full"));
+    }
+
+    #[test]
+    fn edited_configs_keep_their_keys() {
+        let mut panel = DynamicPanel::new("sidebar");
+        for key in ["rail", "full", "sheet"] {
+            panel.push_panel_named(key, SinglePanelCfg::left().into());
+        }
+        let side = |panel: &DynamicPanel<'_>, index: usize| panel.panels()[index].expanded().side();
+        if let Some(PanelCfg::Single(cfg)) = panel.panel_mut(0) {
+            *cfg = cfg.to_right();
+        }
+        assert_eq!(side(&panel, 0), Side::Right);
+        assert!(panel
+            .replace_panel(2, SinglePanelCfg::bottom().into())
+            .is_some());
+        assert!(panel
+            .replace_panel(3, SinglePanelCfg::top().into())
+            .is_none());
+        assert!(panel.remove_panel(1).is_some());
+        assert_eq!(panel.panels().len(), 2);
+        assert_eq!(panel.index_of("rail"), Some(0));
+        assert_eq!(panel.index_of("full"), None);
+        assert_eq!(panel.index_of("sheet"), Some(1));
+        assert_eq!(side(&panel, 1), Side::Bottom);
+    }
+
+    #[test]
+    fn named_choices_select_their_config() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            let mut panel = DynamicPanel::new("sidebar").with_named_choice_function(|ctx| {
+                if ctx.screen_rect().width() < 600.0 {
+                    "compact"
+                } else {
+                    "wide"
+                }
+            });
+            panel.push_panel_named("wide", SinglePanelCfg::left().into());
+            panel.push_panel_named("compact", SinglePanelCfg::top().into());
+            panel
+        }
+        assert_eq!(shown(sidebar, egui::vec2(800.0, 600.0)).unwrap().index, 0);
+        assert_eq!(shown(sidebar, egui::vec2(400.0, 600.0)).unwrap().index, 1);
+    }
+
+    #[test]
+    fn choosing_none_hides_the_panel() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_optional_choice_function(|ctx| {
+                    (ctx.screen_rect().width() >= 600.0).then_some(0)
+                })
+        }
+        assert_eq!(shown(sidebar, egui::vec2(800.0, 600.0)).unwrap().index, 0);
+        assert_eq!(shown(sidebar, egui::vec2(400.0, 600.0)), None);
+    }
+
+    #[test]
+    fn missing_configs_fall_back() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into(), SinglePanelCfg::top().into()])
+                .with_choice_function(|_| 5)
+                .with_fallback_index(1)
+        }
+        assert_eq!(shown(sidebar, egui::vec2(800.0, 600.0)).unwrap().index, 1);
+    }
+
+    #[test]
+    fn state_reflects_the_shown_panel() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into(), SinglePanelCfg::top().into()])
+                .with_choice_function(|_| 1)
+        }
+        let mut probe = LayoutProbe::new();
+        let app = |ctx: &Context| {
+            sidebar(ctx).show_dynamic_collapsible(ctx, |ui| ui.label("…"));
+        };
+        probe.run(egui::vec2(800.0, 600.0), app);
+        let ctx = probe.context().clone();
+        let state = sidebar(&ctx).state(&ctx);
+        assert_eq!(state.current_index(), Some(1));
+        assert!(state.is_expanded() && !state.is_detached());
+        assert_eq!(state.pinned_index(), None);
+        sidebar(&ctx).set_expanded(&ctx, false);
+        sidebar(&ctx).pin_index(&ctx, Some(0));
+        probe.run(egui::vec2(800.0, 600.0), app);
+        let state = sidebar(&ctx).state(&ctx);
+        assert_eq!(state.current_index(), Some(0));
+        assert!(state.is_collapsed());
+        assert_eq!(state.pinned_index(), Some(0));
+    }
+
+    #[test]
+    fn how_expanded_follows_the_animation() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_choice_function(|_| 0)
+        }
+        let ctx = Context::default();
+        let how_expanded = |time: f64, expanded: bool| {
+            frame(&ctx, time, vec![], |ctx| {
+                sidebar(ctx).show_dynamic_animated(ctx, expanded, |ui| ui.label("…"));
+            });
+            sidebar(&ctx).how_expanded(&ctx)
+        };
+        how_expanded(0.0, true);
+        assert_eq!(how_expanded(1.0, true), 1.0);
+        how_expanded(2.0, false);
+        let closing = how_expanded(2.03, false);
+        if cfg!(feature = "animation") {
+            assert!(closing > 0.0 && closing < 1.0);
+        } else {
+            assert_eq!(closing, 0.0);
+        }
+        assert_eq!(how_expanded(3.0, false), 0.0);
+    }
+
+    #[test]
+    fn sizes_are_forgotten_after_a_while_hidden() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().default_size(150.0).into()])
+                .with_choice_function(|_| 0)
+                .forget_after(10.0)
+        }
+        let ctx = Context::default();
+        let width = |time: f64| {
+            frame(&ctx, time, vec![], |ctx| {
+                sidebar(ctx).show_dynamic(ctx, |ui| ui.allocate_space(ui.available_size()));
+            });
+            let id = sidebar(&ctx).panel_id(&ctx);
+            PanelState::load(&ctx, id).unwrap().rect.width()
+        };
+        let resize = |width: f32| {
+            let id = sidebar(&ctx).panel_id(&ctx);
+            let mut rect = PanelState::load(&ctx, id).unwrap().rect;
+            rect.set_width(width);
+            ctx.data_mut(|d| d.insert_persisted(id, PanelState { rect }));
+        };
+        assert_eq!(width(0.0), 150.0);
+        resize(300.0);
+        assert_eq!(width(5.0), 300.0);
+        assert_eq!(width(14.0), 300.0);
+        assert_eq!(width(30.0), 150.0);
+    }
+
+    #[test]
+    fn panels_inside_containers_have_their_own_size() {
+        let content_widths = |namespace: bool| {
+            let ctx = Context::default();
+            let widths = std::cell::Cell::new([0.0; 2]);
+            let app = |ctx: &Context| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    for (i, x) in [0.0, 400.0].into_iter().enumerate() {
+                        let rect =
+                            Rect::from_min_size(egui::pos2(x, 0.0), egui::vec2(400.0, 600.0));
+                        ui.allocate_new_ui(UiBuilder::new().id_salt(i).max_rect(rect), |ui| {
+                            let panel = DynamicPanel::new("sidebar")
+                                .with_panels([SinglePanelCfg::left().into()])
+                                .namespace_inside(namespace);
+                            panel.show_inside(ui, 0, |ui| {
+                                let mut w = widths.get();
+                                w[i] = ui.allocate_space(ui.available_size()).1.width();
+                                widths.set(w);
+                            });
+                        });
+                    }
+                });
+            };
+            frame(&ctx, 0.0, vec![], app);
+            // The Id of the panel shown last, in the second container.
+            let id = DynamicPanel::new("sidebar").panel_id(&ctx);
+            let mut rect = PanelState::load(&ctx, id).unwrap().rect;
+            rect.set_width(300.0);
+            ctx.data_mut(|d| d.insert_persisted(id, PanelState { rect }));
+            frame(&ctx, 1.0, vec![], app);
+            widths.get()
+        };
+        let [other, resized] = content_widths(true);
+        assert!(other < resized);
+        let [other, resized] = content_widths(false);
+        assert_eq!(other, resized);
+    }
+
+    #[test]
+    fn two_configs_are_stored_inline() {
+        let mut panel = DynamicPanel::new("sidebar").with_panels([
+            SinglePanelCfg::left().into(),
+            SinglePanelCfg::bottom().into(),
+        ]);
+        assert!(!panel.panels.spilled());
+        panel.push_panel(SinglePanelCfg::top().into());
+        assert!(panel.panels.spilled());
+    }
+
+    #[test]
+    fn pinning_a_missing_config_is_ignored() {
+        fn sidebar<'a>() -> DynamicPanel<'a> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().into(),
+                    SinglePanelCfg::right().into(),
+                ])
+                .with_choice_function(|_| 1)
+                .strict(true)
+        }
+        let mut probe = LayoutProbe::new();
+        sidebar().pin_index(probe.context(), Some(0));
+        sidebar().pin_index(probe.context(), Some(2));
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            sidebar().show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.assert_index(&sidebar(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no config at index 2")]
+    fn strict_panels_panic_on_missing_configs() {
+        frame(&Context::default(), 0.0, vec![], |ctx| {
+            let panel = DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .strict(true);
+            panel.show(ctx, 2, |ui| ui.label("…"));
+        });
+    }
+
+    #[test]
+    fn lenient_panels_show_nothing_for_missing_configs() {
+        frame(&Context::default(), 0.0, vec![], |ctx| {
+            let panel = DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .strict(false);
+            assert!(panel.show(ctx, 2, |ui| ui.label("…")).is_none());
+        });
+    }
+
+    #[test]
+    fn pinned_and_preferred_indices_fall_back() {
+        fn sidebar<'a>(configs: usize) -> DynamicPanel<'a> {
+            DynamicPanel::new("sidebar")
+                .with_panels((0..configs).map(|_| SinglePanelCfg::left().into()))
+                .with_choice_function(|_| 0)
+                .with_fallback_index(0)
+                .strict(true)
+        }
+        let mut probe = LayoutProbe::new();
+        sidebar(3).pin_index(probe.context(), Some(2));
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            sidebar(1).show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.assert_index(&sidebar(1), 0);
+
+        sidebar(3).pin_index(probe.context(), None);
+        sidebar(3).prefer_index(probe.context(), 2);
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            sidebar(1).show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.assert_index(&sidebar(1), 0);
+    }
+
+    #[test]
+    fn touch_friendly_separators_grab_from_further_away() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_choice_function(|_| 0)
+        }
+        fn touch_sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().touch_friendly().into()])
+                .with_choice_function(|_| 0)
+        }
+        assert!(resizable_from(sidebar, 2.0));
+        assert!(!resizable_from(sidebar, 14.0));
+        assert!(resizable_from(touch_sidebar, 14.0));
+    }
+
+    #[test]
+    fn touch_mode_enlarges_separators_and_buttons() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([SinglePanelCfg::left().into()])
+                .with_choice_function(|_| 0)
+                .touch_mode(true)
+        }
+        assert!(resizable_from(sidebar, 14.0));
+        let mut button = Rect::NOTHING;
+        LayoutProbe::new()
+            .frames(1)
+            .run(egui::vec2(800.0, 600.0), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    button = sidebar(ctx).collapse_button(ui).rect;
+                });
+            });
+        assert_eq!(button.size(), egui::Vec2::splat(TOUCH_BUTTON_SIZE));
+    }
+
+    #[test]
+    fn arrow_keys_resize_the_focused_separator() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            let mut cfg = SinglePanelCfg::left();
+            cfg.keyboard_resize_step = Some(10.0);
+            DynamicPanel::new("sidebar")
+                .with_panels([cfg.into()])
+                .with_choice_function(|_| 0)
+        }
+        let ctx = Context::default();
+        let app = |ctx: &Context| {
+            // Resizable egui panels shrink to their content.
+            sidebar(ctx).show_dynamic(ctx, |ui| ui.allocate_space(ui.available_size()));
+        };
+        let width = |ctx: &Context| {
+            PanelState::load(ctx, sidebar(ctx).id())
+                .unwrap()
+                .rect
+                .width()
+        };
+        frame(&ctx, 0.0, vec![], app);
+        let before = width(&ctx);
+        ctx.memory_mut(|m| m.request_focus(sidebar(&ctx).id().with("__separator")));
+        let press = |key| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        };
+        let keys = vec![
+            press(Key::ArrowRight),
+            press(Key::ArrowRight),
+            press(Key::ArrowLeft),
+        ];
+        frame(&ctx, 0.1, keys, app);
+        frame(&ctx, 0.2, vec![], app);
+        assert_eq!(width(&ctx), before + 10.0);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn index_changes_animate_the_panels_closed_and_open() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().into(),
+                    SinglePanelCfg::right().into(),
+                ])
+                .with_choice_function(chosen)
+                .animate_index_changes(true)
+        }
+        fn snapping(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().into(),
+                    SinglePanelCfg::right().into(),
+                ])
+                .with_choice_function(chosen)
+        }
+        let samples = switch(sidebar);
+        assert!(samples
+            .iter()
+            .any(|s| s.progress.is_some_and(|p| p > 0.0 && p < 1.0)));
+        assert!(samples
+            .iter()
+            .any(|s| s.content.is_some_and(|(opacity, _)| opacity < 1.0)));
+        assert!(samples.last().unwrap().progress.is_none());
+        assert!(switch(snapping).iter().all(|s| s.progress.is_none()));
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn crossfade_fades_the_content_in_after_a_switch() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().into(),
+                    SinglePanelCfg::right().into(),
+                ])
+                .with_choice_function(chosen)
+                .crossfade(true)
+        }
+        let opacities: Vec<f32> = switch(sidebar)
+            .iter()
+            .map(|s| s.content.unwrap().0)
+            .collect();
+        assert!(opacities[0] < 0.5);
+        assert!(opacities.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(*opacities.last().unwrap(), 1.0);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn interpolated_sizes_pass_the_sizes_in_between() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().exact_size(150.0).into(),
+                    SinglePanelCfg::left().exact_size(300.0).into(),
+                ])
+                .with_choice_function(chosen)
+                .interpolate_sizes(true)
+        }
+        let widths: Vec<f32> = switch(sidebar)
+            .iter()
+            .map(|s| s.content.unwrap().1)
+            .collect();
+        assert!(widths.iter().any(|&w| w > 160.0 && w < 280.0));
+        assert!(widths.windows(2).all(|w| w[0] <= w[1]));
+        assert!(*widths.last().unwrap() > 280.0);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn finished_transitions_are_reported_once() {
+        static ENDS: std::sync::Mutex<Vec<AnimationEnd>> = std::sync::Mutex::new(Vec::new());
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().into(),
+                    SinglePanelCfg::right().into(),
+                ])
+                .with_choice_function(chosen)
+                .animate_index_changes(true)
+                .on_animation_end(|_, end| ENDS.lock().unwrap().push(end))
+        }
+        switch(sidebar);
+        let end = AnimationEnd {
+            index: 1,
+            expanded: true,
+        };
+        assert_eq!(*ENDS.lock().unwrap(), [end]);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn unanimated_panels_switch_instantly() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().into(),
+                    SinglePanelCfg::right().into(),
+                ])
+                .with_choice_function(chosen)
+                .animate_index_changes(true)
+                .crossfade(true)
+                .animated(false)
+        }
+        assert!(switch(sidebar)
+            .iter()
+            .all(|s| s.progress.is_none() && s.content.is_some_and(|(opacity, _)| opacity == 1.0)));
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn reduced_motion_jumps_to_the_new_size() {
+        fn sidebar(ctx: &Context) -> DynamicPanel<'_> {
+            set_reduce_motion(ctx, true);
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().exact_size(150.0).into(),
+                    SinglePanelCfg::left().exact_size(300.0).into(),
+                ])
+                .with_choice_function(chosen)
+                .interpolate_sizes(true)
+                .crossfade(true)
+        }
+        fn overridden(ctx: &Context) -> DynamicPanel<'_> {
+            sidebar(ctx).reduce_motion(false)
+        }
+        let samples = switch(sidebar);
+        assert!(samples
+            .iter()
+            .all(|s| s.content.is_some_and(|(_, width)| width > 280.0)));
+        assert!(samples
+            .iter()
+            .any(|s| s.content.is_some_and(|(opacity, _)| opacity < 1.0)));
+        assert!(switch(overridden)
+            .iter()
+            .any(|s| s.content.is_some_and(|(_, width)| width < 280.0)));
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn chained_transitions_pass_the_sizes_on_the_way() {
+        fn sidebar(_: &Context) -> DynamicPanel<'_> {
+            DynamicPanel::new("sidebar")
+                .with_panels([
+                    SinglePanelCfg::left().exact_size(100.0).into(),
+                    SinglePanelCfg::left().exact_size(200.0).into(),
+                    SinglePanelCfg::left().exact_size(300.0).into(),
+                ])
+                .with_choice_function(chosen)
+                .interpolate_sizes(true)
+                .chain([0, 2, 1])
+        }
+        let widths: Vec<f32> = switch(sidebar)
+            .iter()
+            .map(|s| s.content.unwrap().1)
+            .collect();
+        assert!(widths.iter().any(|&w| w > 250.0));
+        assert!((widths.last().unwrap() - 200.0).abs() < 20.0);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn chain_path_passes_the_configs_in_between() {
+        let panel = DynamicPanel::new("sidebar")
+            .with_panels((0..5).map(|_| SinglePanelCfg::left().into()))
+            .chain([4, 0, 2]);
+        assert_eq!(panel.chain_path(4, 2).as_slice(), [4, 0, 2]);
+        assert_eq!(panel.chain_path(2, 4).as_slice(), [2, 0, 4]);
+        assert_eq!(panel.chain_path(0, 2).as_slice(), [0, 2]);
+        assert_eq!(panel.chain_path(1, 2).as_slice(), [1, 2]);
+        assert_eq!(panel.chain_path(3, 1).as_slice(), [3, 1]);
+    }
 }
@@ -0,0 +1,55 @@
+/// Declares a [`DynamicPanel`](crate::DynamicPanel) with its configs and breakpoints in one block.
+///
+/// ```
+/// use egui_dpanel::dpanel;
+///
+/// let ctx = egui::Context::default();
+/// let input = egui::RawInput {
+///     screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 300.0))),
+///     ..Default::default()
+/// };
+/// let _ = ctx.run(input, |ctx| {
+///     let sidebar = dpanel! {
+///         "sidebar":
+///         left(250.0) @ width >= 700.0,
+///         top(48.0) @ portrait,
+///         bottom(120.0) otherwise
+///     };
+///     assert!(sidebar.show_dynamic(ctx, |ui| ui.label("…")).is_some());
+///     assert_eq!(sidebar.current_index(ctx), Some(2));
+/// });
+/// ```
+///
+/// Each entry is a side (`left`, `right`, `top` or `bottom`) with the default size of its config, and either a
+/// breakpoint comparing the screen's `width` or `height`, a condition of [`crate::choice`] (`portrait`,
+/// `landscape`, `is_touch`), or `otherwise` for a last entry that is shown when no breakpoint holds. The first
+/// entry whose breakpoint holds is shown. Expands to [`DynamicPanel::with_rules`](crate::DynamicPanel::with_rules),
+/// so the result can be configured further with the usual builders.
+#[macro_export]
+macro_rules! dpanel {
+    ($name:literal : $($entries:tt)*) => {{
+        let mut panel = $crate::DynamicPanel::new($name);
+        let mut rules = ::std::vec::Vec::new();
+        $crate::dpanel!(@entries panel rules $($entries)*);
+        panel.with_rules(rules)
+    }};
+    (@entries $panel:ident $rules:ident $side:ident ($size:expr) otherwise $(,)?) => {
+        let index = $panel.push_panel($crate::dpanel!(@cfg $side $size));
+        $rules.push($crate::choice::always().then_index(index));
+    };
+    (@entries $panel:ident $rules:ident $side:ident ($size:expr) @ $condition:ident $(, $($rest:tt)*)?) => {
+        let index = $panel.push_panel($crate::dpanel!(@cfg $side $size));
+        $rules.push($crate::choice::$condition().then_index(index));
+        $crate::dpanel!(@entries $panel $rules $($($rest)*)?);
+    };
+    (@entries $panel:ident $rules:ident $side:ident ($size:expr) @ $axis:ident $op:tt $value:expr $(, $($rest:tt)*)?) => {
+        let index = $panel.push_panel($crate::dpanel!(@cfg $side $size));
+        let condition = $crate::choice::Condition::new(move |ctx| $crate::breakpoint_rect(ctx).$axis() $op $value as f32);
+        $rules.push(condition.then_index(index));
+        $crate::dpanel!(@entries $panel $rules $($($rest)*)?);
+    };
+    (@entries $panel:ident $rules:ident) => {};
+    (@cfg $side:ident $size:expr) => {
+        $crate::SinglePanelCfg::$side().default_size($size as f32).into()
+    };
+}
@@ -0,0 +1,98 @@
+//! Notifications about layout changes of panels, e.g. for analytics or an undo history.
+
+use crate::state::DynamicPanelState;
+use crate::{DynamicPanel, Side};
+use egui::containers::panel::PanelState;
+use egui::{Context, Id};
+use std::sync::Arc;
+
+/// Receives the layout changes of the panels it is registered on with [`DynamicPanel::observer`] or
+/// [`crate::PanelGroup::observer`]. Panels are identified by their name. All methods do nothing by default.
+///
+/// Changes are noticed once per frame, no matter whether the user, the app or the crate made them. The first frame
+/// a panel is observed in reports its initial state as changes from an expanded panel without a config or side.
+pub trait LayoutObserver {
+    /// The panel switched from config `from` to config `to`. `from` is `None` if no config was shown before.
+    fn on_index_changed(&self, _panel: &str, _from: Option<usize>, _to: usize) {}
+
+    /// The panel was collapsed or expanded, including being hidden by a [`crate::PanelGroup`].
+    fn on_collapse(&self, _panel: &str, _collapsed: bool) {}
+
+    /// The width (left/right) or height (top/bottom) of the docked panel changed, e.g. by dragging its separator.
+    fn on_resize(&self, _panel: &str, _size: f32) {}
+
+    /// The panel moved to another side, or out of its dock (`None`) into its own window or an overlay config.
+    fn on_dock_moved(&self, _panel: &str, _side: Option<Side>) {}
+}
+
+/// What the observers saw last.
+#[derive(Clone, Copy)]
+struct Observed {
+    index: Option<usize>,
+    collapsed: bool,
+    size: Option<f32>,
+    side: Option<Side>,
+}
+
+impl Observed {
+    fn load(ctx: &Context, panel: &DynamicPanel<'_>) -> Self {
+        let id = panel.id();
+        let state = DynamicPanelState::load(ctx, id);
+        let index = state.current_index();
+        let side = index
+            .and_then(|index| panel.docked_side(ctx, index))
+            .filter(|_| !state.detached);
+        // Interpolating transitions store intermediate sizes, only the final one counts.
+        let size = side.filter(|_| !state.is_transitioning()).and_then(|side| {
+            Some(side.rect_size(PanelState::load(ctx, panel.panel_id(ctx))?.rect))
+        });
+        Self {
+            index,
+            collapsed: !state.is_expanded(),
+            size,
+            side,
+        }
+    }
+}
+
+/// Reports the changes of `panel` since the last call with the same `key` in the same viewport to `observers`.
+pub(crate) fn notify(
+    ctx: &Context,
+    panel: &DynamicPanel<'_>,
+    key: Id,
+    observers: &[Arc<dyn LayoutObserver>],
+) {
+    if observers.is_empty() {
+        return;
+    }
+    let key = key.with(ctx.viewport_id());
+    let now = Observed::load(ctx, panel);
+    let before = ctx.data_mut(|d| {
+        let before = d.get_temp::<Observed>(key);
+        d.insert_temp(key, now);
+        before
+    });
+    let before = before.unwrap_or(Observed {
+        index: None,
+        collapsed: false,
+        size: None,
+        side: None,
+    });
+    let name = panel.name();
+    for observer in observers {
+        if let Some(index) = now.index.filter(|&index| Some(index) != before.index) {
+            observer.on_index_changed(name, before.index, index);
+        }
+        if now.collapsed != before.collapsed {
+            observer.on_collapse(name, now.collapsed);
+        }
+        if now.side != before.side {
+            observer.on_dock_moved(name, now.side);
+        }
+        if let (Some(size), Some(previous)) = (now.size, before.size) {
+            if now.side == before.side && (size - previous).abs() >= 0.5 {
+                observer.on_resize(name, size);
+            }
+        }
+    }
+}
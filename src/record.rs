@@ -0,0 +1,291 @@
+//! Recording the layout changes of panels and replaying them, to reproduce layout bugs reported by users.
+//!
+//! A [`LayoutRecorder`] is registered as [observer](crate::LayoutObserver) and keeps a timeline of what happened.
+//! The timeline can be saved with [`LayoutRecorder::to_text`], and a [`LayoutReplay`] applies it again frame by
+//! frame at the original pace.
+
+use crate::state::DynamicPanelState;
+use crate::{DynamicPanel, LayoutObserver, Side};
+use egui::containers::panel::PanelState;
+use egui::Context;
+use std::sync::Mutex;
+
+/// A layout change of the panel `panel`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutEvent {
+    IndexChanged { panel: String, index: usize },
+    Collapsed { panel: String, collapsed: bool },
+    Resized { panel: String, size: f32 },
+    DockMoved { panel: String, side: Option<Side> },
+}
+
+impl LayoutEvent {
+    pub fn panel(&self) -> &str {
+        match self {
+            Self::IndexChanged { panel, .. }
+            | Self::Collapsed { panel, .. }
+            | Self::Resized { panel, .. }
+            | Self::DockMoved { panel, .. } => panel,
+        }
+    }
+}
+
+/// A [`LayoutEvent`] with the `InputState::time` it happened at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedEvent {
+    pub time: f64,
+    pub event: LayoutEvent,
+}
+
+/// Observer recording the layout changes of the panels it is registered on.
+pub struct LayoutRecorder {
+    ctx: Context,
+    events: Mutex<Vec<RecordedEvent>>,
+}
+
+impl LayoutRecorder {
+    pub fn new(ctx: &Context) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            events: Mutex::default(),
+        }
+    }
+
+    /// The events recorded so far.
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// The recorded events, one per line, to be read again by [`LayoutReplay::from_text`].
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for RecordedEvent { time, event } in self.events.lock().unwrap().iter() {
+            let (kind, value) = match event {
+                LayoutEvent::IndexChanged { index, .. } => ("index", index.to_string()),
+                LayoutEvent::Collapsed { collapsed, .. } => ("collapsed", collapsed.to_string()),
+                LayoutEvent::Resized { size, .. } => ("size", size.to_string()),
+                LayoutEvent::DockMoved { side, .. } => ("side", side_name(*side).to_owned()),
+            };
+            let panel = event.panel().replace(['\t', '\n'], " ");
+            text += &format!("{time}\t{panel}\t{kind}\t{value}\n");
+        }
+        text
+    }
+
+    fn push(&self, event: LayoutEvent) {
+        let time = self.ctx.input(|i| i.time);
+        self.events
+            .lock()
+            .unwrap()
+            .push(RecordedEvent { time, event });
+    }
+}
+
+impl LayoutObserver for LayoutRecorder {
+    fn on_index_changed(&self, panel: &str, _from: Option<usize>, to: usize) {
+        let panel = panel.to_owned();
+        self.push(LayoutEvent::IndexChanged { panel, index: to });
+    }
+
+    fn on_collapse(&self, panel: &str, collapsed: bool) {
+        let panel = panel.to_owned();
+        self.push(LayoutEvent::Collapsed { panel, collapsed });
+    }
+
+    fn on_resize(&self, panel: &str, size: f32) {
+        let panel = panel.to_owned();
+        self.push(LayoutEvent::Resized { panel, size });
+    }
+
+    fn on_dock_moved(&self, panel: &str, side: Option<Side>) {
+        let panel = panel.to_owned();
+        self.push(LayoutEvent::DockMoved { panel, side });
+    }
+}
+
+/// Applies recorded events to panels again, with the same delays between them as when they were recorded.
+pub struct LayoutReplay {
+    events: Vec<RecordedEvent>,
+    next: usize,
+    /// `InputState::time` the replay started at.
+    started_at: Option<f64>,
+    /// Names of the panels whose index the replay pinned, released once it is finished.
+    pinned: Vec<String>,
+}
+
+impl LayoutReplay {
+    pub fn new(events: Vec<RecordedEvent>) -> Self {
+        Self {
+            events,
+            next: 0,
+            started_at: None,
+            pinned: Vec::new(),
+        }
+    }
+
+    /// Reads events written by [`LayoutRecorder::to_text`], skipping lines that can't be parsed.
+    pub fn from_text(text: &str) -> Self {
+        let events = text.lines().filter_map(parse_line).collect();
+        Self::new(events)
+    }
+
+    /// Whether all events were applied.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.events.len()
+    }
+
+    /// Applies the events that are due to the panels named in them. Call once per frame before showing `panels`.
+    ///
+    /// Index changes pin the index (see [`DynamicPanel::pin_index`]) so the choice function doesn't undo them, and
+    /// are ignored if the panel has no config at the index. Once the replay is finished the pins are released, the
+    /// replayed configs stay until the choice function switches to another one.
+    /// Moves to another side follow from the index changes and are not applied on their own.
+    pub fn apply(&mut self, ctx: &Context, panels: &[&DynamicPanel]) {
+        let now = ctx.input(|i| i.time);
+        let Some(first) = self.events.first() else {
+            return;
+        };
+        let offset = first.time - *self.started_at.get_or_insert(now);
+        while let Some(RecordedEvent { time, event }) = self.events.get(self.next) {
+            if time - offset > now {
+                ctx.request_repaint_after_secs((time - offset - now) as f32);
+                break;
+            }
+            self.next += 1;
+            let Some(panel) = panels.iter().find(|p| p.name() == event.panel()) else {
+                continue;
+            };
+            match event {
+                LayoutEvent::IndexChanged { index, .. } if *index < panel.panels().len() => {
+                    panel.pin_index(ctx, Some(*index));
+                    if !self.pinned.iter().any(|name| name == panel.name()) {
+                        self.pinned.push(panel.name().to_owned());
+                    }
+                }
+                LayoutEvent::IndexChanged { .. } => continue,
+                LayoutEvent::Collapsed { collapsed, .. } => {
+                    let mut state = DynamicPanelState::load(ctx, panel.id());
+                    state.collapsed = *collapsed;
+                    state.store(ctx, panel.id());
+                }
+                LayoutEvent::Resized { size, .. } => {
+                    let side = panel
+                        .current_index(ctx)
+                        .and_then(|index| panel.docked_side(ctx, index));
+                    let id = panel.panel_id(ctx);
+                    if let (Some(side), Some(PanelState { mut rect })) =
+                        (side, PanelState::load(ctx, id))
+                    {
+                        side.set_rect_size(&mut rect, *size);
+                        ctx.data_mut(|d| d.insert_persisted(id, PanelState { rect }));
+                    }
+                }
+                LayoutEvent::DockMoved { .. } => {}
+            }
+            ctx.request_repaint();
+        }
+        if self.is_finished() {
+            self.release(ctx, panels);
+        }
+    }
+
+    /// Unpins the indices pinned by the replay, showing them until the choice function switches.
+    fn release(&mut self, ctx: &Context, panels: &[&DynamicPanel]) {
+        for name in self.pinned.drain(..) {
+            let Some(panel) = panels.iter().find(|p| p.name() == name) else {
+                continue;
+            };
+            let pinned = DynamicPanelState::load(ctx, panel.id()).pinned_index;
+            panel.pin_index(ctx, None);
+            if let Some(index) = pinned {
+                panel.prefer_index(ctx, index);
+            }
+        }
+    }
+}
+
+fn side_name(side: Option<Side>) -> &'static str {
+    match side {
+        Some(Side::Left) => "left",
+        Some(Side::Right) => "right",
+        Some(Side::Top) => "top",
+        Some(Side::Bottom) => "bottom",
+        None => "none",
+    }
+}
+
+fn parse_line(line: &str) -> Option<RecordedEvent> {
+    let mut parts = line.split('\t');
+    let time = parts.next()?.parse().ok()?;
+    let panel = parts.next()?.to_owned();
+    let kind = parts.next()?;
+    let value = parts.next()?;
+    let event = match kind {
+        "index" => LayoutEvent::IndexChanged {
+            panel,
+            index: value.parse().ok()?,
+        },
+        "collapsed" => LayoutEvent::Collapsed {
+            panel,
+            collapsed: value.parse().ok()?,
+        },
+        "size" => LayoutEvent::Resized {
+            panel,
+            size: value.parse().ok()?,
+        },
+        "side" => LayoutEvent::DockMoved {
+            panel,
+            side: match value {
+                "left" => Some(Side::Left),
+                "right" => Some(Side::Right),
+                "top" => Some(Side::Top),
+                "bottom" => Some(Side::Bottom),
+                "none" => None,
+                _ => return None,
+            },
+        },
+        _ => return None,
+    };
+    Some(RecordedEvent { time, event })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_round_trip() {
+        let ctx = Context::default();
+        let recorder = LayoutRecorder::new(&ctx);
+        recorder.on_index_changed("side\tbar", None, 1);
+        recorder.on_collapse("sidebar", true);
+        recorder.on_resize("sidebar", 212.5);
+        recorder.on_dock_moved("sidebar", Some(Side::Right));
+        recorder.on_dock_moved("sidebar", None);
+
+        let replay = LayoutReplay::from_text(&recorder.to_text());
+        let mut expected = recorder.events();
+        // Tabs and newlines would break the line format, they are written as spaces.
+        expected[0].event = LayoutEvent::IndexChanged {
+            panel: "side bar".to_owned(),
+            index: 1,
+        };
+        assert_eq!(replay.events, expected);
+    }
+
+    #[test]
+    fn from_text_skips_broken_lines() {
+        let replay =
+            LayoutReplay::from_text("0.5\tsidebar\tindex\t2\nnonsense\n1\tsidebar\tsize\twide\n");
+        assert_eq!(
+            replay.events,
+            [RecordedEvent {
+                time: 0.5,
+                event: LayoutEvent::IndexChanged {
+                    panel: "sidebar".to_owned(),
+                    index: 2,
+                },
+            }]
+        );
+    }
+}
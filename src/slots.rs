@@ -0,0 +1,125 @@
+//! Panel content split into a fixed header, a scrolling body and a footer pinned to the bottom.
+
+use egui::{Id, ScrollArea, Ui};
+
+/// Lays out panel content in three slots: the header stays at the top, the footer at the bottom of the panel,
+/// and the body scrolls in between, however the panel is resized.
+///
+/// The footer is measured in the previous pass, a change of its height discards the pass so it never jumps.
+///
+/// ```ignore
+/// dpanel.show_dynamic(ctx, |ui| {
+///     PanelSlots::new("inspector").show(
+///         ui,
+///         |ui| ui.heading("Inspector"),
+///         |ui| ui.label("…"),
+///         |ui| ui.button("Apply"),
+///     )
+/// });
+/// ```
+pub struct PanelSlots {
+    id: Id,
+    separators: bool,
+}
+
+impl PanelSlots {
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            separators: true,
+        }
+    }
+
+    /// Whether separators are drawn between the slots, on by default.
+    pub fn separators(mut self, separators: bool) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Shows the slots, returning what each of them returned.
+    pub fn show<H, B, F>(
+        self,
+        ui: &mut Ui,
+        header: impl FnOnce(&mut Ui) -> H,
+        body: impl FnOnce(&mut Ui) -> B,
+        footer: impl FnOnce(&mut Ui) -> F,
+    ) -> (H, B, F) {
+        let footer_id = self.id.with("__footer_height");
+        let header = header(ui);
+        if self.separators {
+            ui.separator();
+        }
+
+        let footer_height: f32 = ui.data(|d| d.get_temp(footer_id)).unwrap_or_default();
+        let spacing = ui.spacing().item_spacing.y;
+        let body_height = (ui.available_height() - footer_height - spacing).max(0.0);
+        let body = ScrollArea::vertical()
+            .id_salt(self.id.with("__body"))
+            .max_height(body_height)
+            .min_scrolled_height(body_height)
+            .auto_shrink([false, false])
+            .show(ui, body)
+            .inner;
+
+        let top = ui.cursor().top();
+        if self.separators {
+            ui.separator();
+        }
+        let footer = footer(ui);
+        let height = ui.min_rect().bottom() - top;
+        if (height - footer_height).abs() > 0.5 {
+            ui.data_mut(|d| d.insert_temp(footer_id, height));
+            ui.ctx().request_discard("PanelSlots footer height changed");
+        }
+        (header, body, footer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Context, RawInput, Rect};
+
+    /// Rects of the header, the body and the footer of slots filling a screen 600 points high, with a body of
+    /// `rows` labels.
+    fn slot_rects(rows: usize) -> (Rect, Rect, Rect) {
+        let ctx = Context::default();
+        let mut rects = (Rect::NOTHING, Rect::NOTHING, Rect::NOTHING);
+        for _ in 0..3 {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(400.0, 600.0),
+                )),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let (header, body, footer) = PanelSlots::new("slots").show(
+                        ui,
+                        |ui| ui.heading("Header").rect,
+                        |ui| {
+                            for row in 0..rows {
+                                ui.label(format!("Row {row}"));
+                            }
+                            ui.min_rect()
+                        },
+                        |ui| ui.button("Footer").rect,
+                    );
+                    rects = (header, body, footer);
+                });
+            });
+        }
+        rects
+    }
+
+    #[test]
+    fn footer_stays_at_the_bottom() {
+        for rows in [1, 200] {
+            let (header, body, footer) = slot_rects(rows);
+            assert!(header.top() < 20.0);
+            assert!(footer.bottom() > 580.0 && footer.bottom() <= 600.0);
+            assert!(body.top() > header.bottom());
+        }
+    }
+}
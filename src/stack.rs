@@ -0,0 +1,158 @@
+//! Two content regions sharing one docked panel, separated by a draggable splitter.
+
+use crate::{PanelCfg, SinglePanelCfg};
+use egui::{CursorIcon, Id, Rect, Sense, Ui, UiBuilder};
+
+/// A docked panel holding two regions, e.g. a project tree above the properties of the selected item.
+/// Left/right panels stack the regions vertically, top/bottom panels place them side by side.
+///
+/// The content closure of the [`crate::DynamicPanel`] is called once per region, use [`stack_region`] to tell
+/// them apart. The splitter position is kept in egui's memory, as a ratio of the panel size.
+pub struct StackedPanelCfg {
+    pub panel: SinglePanelCfg,
+    /// Share of the first region when the splitter wasn't moved yet, from 0.0 to 1.0.
+    pub default_ratio: f32,
+    /// Smallest size of either region in points.
+    pub min_region_size: f32,
+}
+
+impl StackedPanelCfg {
+    pub fn new(panel: SinglePanelCfg) -> Self {
+        Self {
+            panel,
+            default_ratio: 0.5,
+            min_region_size: 32.0,
+        }
+    }
+
+    pub fn default_ratio(mut self, ratio: f32) -> Self {
+        self.default_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl From<StackedPanelCfg> for PanelCfg {
+    fn from(cfg: StackedPanelCfg) -> Self {
+        PanelCfg::Stacked(cfg)
+    }
+}
+
+/// Region of a [`StackedPanelCfg`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum StackRegion {
+    /// The top region, or the left one in top/bottom panels.
+    First,
+    Second,
+}
+
+fn region_id() -> Id {
+    Id::new("egui_dpanel_stack_region")
+}
+
+/// The region of a [`StackedPanelCfg`] the content closure is currently called for,
+/// `None` if the current config isn't stacked and the content is shown once.
+pub fn stack_region(ui: &Ui) -> Option<StackRegion> {
+    ui.data(|d| d.get_temp::<Option<StackRegion>>(region_id()))
+        .flatten()
+}
+
+/// Splits the panel Ui and calls `content` for both regions. Returns the result of the first one.
+pub(crate) fn show_stacked<R>(
+    ui: &mut Ui,
+    panel_id: Id,
+    cfg: &StackedPanelCfg,
+    content: &impl Fn(&mut Ui) -> R,
+) -> R {
+    let id = panel_id.with("__stack");
+    let vertical = cfg.panel.side.is_lr();
+    let rect = ui.available_rect_before_wrap();
+    let length = if vertical {
+        rect.height()
+    } else {
+        rect.width()
+    };
+    let gap = ui.spacing().item_spacing.y.max(ui.spacing().item_spacing.x);
+    let min = (cfg.min_region_size / length).min(0.5);
+    let mut ratio: f32 = ui.data_mut(|d| *d.get_persisted_mut_or(id, cfg.default_ratio));
+    ratio = ratio.clamp(min, 1.0 - min);
+
+    let split = |ratio: f32| {
+        let at = if vertical {
+            rect.top() + ratio * length
+        } else {
+            rect.left() + ratio * length
+        };
+        if vertical {
+            (
+                Rect::from_min_max(rect.min, egui::pos2(rect.right(), at - gap / 2.0)),
+                Rect::from_x_y_ranges(rect.x_range(), at - gap / 2.0..=at + gap / 2.0),
+                Rect::from_min_max(egui::pos2(rect.left(), at + gap / 2.0), rect.max),
+            )
+        } else {
+            (
+                Rect::from_min_max(rect.min, egui::pos2(at - gap / 2.0, rect.bottom())),
+                Rect::from_x_y_ranges(at - gap / 2.0..=at + gap / 2.0, rect.y_range()),
+                Rect::from_min_max(egui::pos2(at + gap / 2.0, rect.top()), rect.max),
+            )
+        }
+    };
+
+    let (_, splitter, _) = split(ratio);
+    let radius = ui.style().interaction.resize_grab_radius_side;
+    let grab = if vertical {
+        splitter.expand2(egui::vec2(0.0, radius))
+    } else {
+        splitter.expand2(egui::vec2(radius, 0.0))
+    };
+    let response = ui.interact(grab, id.with("splitter"), Sense::drag());
+    if let Some(pos) = response
+        .interact_pointer_pos()
+        .filter(|_| response.dragged())
+    {
+        let offset = if vertical {
+            pos.y - rect.top()
+        } else {
+            pos.x - rect.left()
+        };
+        ratio = (offset / length).clamp(min, 1.0 - min);
+        ui.data_mut(|d| d.insert_persisted(id, ratio));
+    }
+    if response.hovered() || response.dragged() {
+        ui.ctx().set_cursor_icon(if vertical {
+            CursorIcon::ResizeVertical
+        } else {
+            CursorIcon::ResizeHorizontal
+        });
+    }
+
+    let (first, splitter, second) = split(ratio);
+    let stroke = if response.dragged() {
+        ui.visuals().widgets.active.fg_stroke
+    } else if response.hovered() {
+        ui.visuals().widgets.hovered.fg_stroke
+    } else {
+        ui.visuals().widgets.noninteractive.bg_stroke
+    };
+    if vertical {
+        ui.painter()
+            .hline(splitter.x_range(), splitter.center().y, stroke);
+    } else {
+        ui.painter()
+            .vline(splitter.center().x, splitter.y_range(), stroke);
+    }
+
+    let previous = ui.data(|d| d.get_temp::<Option<StackRegion>>(region_id()));
+    let region = |ui: &mut Ui, rect: Rect, region: StackRegion| {
+        ui.data_mut(|d| d.insert_temp(region_id(), Some(region)));
+        ui.allocate_new_ui(UiBuilder::new().max_rect(rect).id_salt(region), |ui| {
+            ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+            content(ui)
+        })
+        .inner
+    };
+    let inner = region(ui, first, StackRegion::First);
+    region(ui, second, StackRegion::Second);
+    ui.data_mut(|d| d.insert_temp(region_id(), previous.flatten()));
+    ui.advance_cursor_after_rect(rect);
+    inner
+}
@@ -0,0 +1,265 @@
+//! Bookkeeping of a [`crate::DynamicPanel`] that has to survive between frames, kept in egui's memory.
+
+use egui::{Context, Id, Rect, ViewportId};
+
+/// State of a [`crate::DynamicPanel`] kept in egui's memory under the panel's Id, so the panel itself can be
+/// rebuilt every frame. Read it with [`crate::DynamicPanel::state`].
+///
+/// A panel shown in several viewports (e.g. the main window and a popped-out one) has a state per viewport, as
+/// the layout depends on the viewport's size. What the user or the app chose for the panel, whether it is
+/// collapsed, hidden, flipped, detached or pinned to a config, is shared by all of them: changing it in one
+/// viewport repaints the others.
+#[derive(Clone, Default)]
+pub struct DynamicPanelState {
+    /// Index chosen in the previous frame.
+    pub(crate) index: Option<usize>,
+    /// Running transition between two indices.
+    #[cfg(feature = "animation")]
+    pub(crate) transition: Option<Transition>,
+    /// `InputState::time` of the last switch to another index.
+    pub(crate) switched_at: Option<f64>,
+    /// Collapsed by the user, see [`crate::DynamicPanel::set_expanded`]. Panels start out expanded.
+    pub(crate) collapsed: bool,
+    /// `InputState::time` an auto-hiding overlay was last revealed.
+    pub(crate) revealed_at: Option<f64>,
+    /// An expand/collapse animation was running in the previous frame.
+    pub(crate) animating: bool,
+    /// Whether the last `show*_animated` call opened or closed the panel.
+    pub(crate) animation_target: Option<bool>,
+    /// Id of the egui panel the docked configs were shown as last, see [`crate::DynamicPanel::namespace_inside`].
+    pub(crate) panel_id: Option<Id>,
+    /// Screen rect and choice of the previous `show_dynamic*` call.
+    pub(crate) last_choice: Option<(Rect, usize)>,
+    /// Shown in its own window instead of docked, see [`crate::DynamicPanel::detach`].
+    pub(crate) detached: bool,
+    /// Pointer position the panel was torn off at, where its window opens, see
+    /// [`crate::DynamicPanel::tear_off_handle`].
+    pub(crate) detached_at: Option<egui::Pos2>,
+    /// Outer rect of the detached window in the previous frame, and whether it was moving then.
+    pub(crate) window_rect: Option<(Rect, bool)>,
+    /// Index shown instead of the one from the choice function, see [`crate::DynamicPanel::pin_index`].
+    pub(crate) pinned_index: Option<usize>,
+    /// Index shown instead of the choice until the choice function switches configs, and the choice it was
+    /// shown against (`None` until the next choice), see `DynamicPanel::prefer_index`.
+    pub(crate) preferred: Option<(usize, Option<usize>)>,
+    /// Hidden by a [`crate::PanelGroup`] for lack of space, independent of `collapsed`.
+    pub(crate) auto_hidden: bool,
+    /// Expanded from a [`crate::PanelToggleBar`], which a [`crate::PanelGroup`] doesn't override until the panel is
+    /// collapsed again.
+    pub(crate) user_shown: bool,
+    /// Whether the panel has a selection, as reported by [`crate::DynamicPanel::report_selection`].
+    pub(crate) selection: Option<bool>,
+    /// Selection of the master panel this detail panel was last expanded or collapsed for.
+    pub(crate) linked_selection: Option<bool>,
+    /// The expanded content is previewed while the collapsed strip is hovered.
+    pub(crate) peeking: bool,
+    /// Expanded by the crate or the app rather than the user, see [`crate::DynamicPanel::auto_expand`].
+    pub(crate) auto_expanded: bool,
+    /// `Context::cumulative_pass_nr` the expanded panel last checked for outside clicks.
+    pub(crate) outside_click_pass: Option<u64>,
+    /// Expanded while a drag and drop payload approached it, see [`crate::DynamicPanel::expand_on_drag`].
+    pub(crate) drag_expanded: bool,
+    /// Hidden from the separator menu: collapsed without showing the strip, until expanded again.
+    pub(crate) hidden: bool,
+    /// Docked on the opposite side of its config, moved there from the separator menu.
+    pub(crate) flipped: bool,
+    /// `InputState::time` the content was last shown, tracked for [`crate::DynamicPanel::forget_after`].
+    pub(crate) shown_at: Option<f64>,
+    /// Content slides to its new place when the panel jumps, set by [`crate::PanelGroup::animate_reordering`].
+    #[cfg(feature = "animation")]
+    pub(crate) slides: bool,
+    /// Pass in which a [`crate::PanelGroup`] with [`crate::PanelGroup::animate_reordering`] last hid a panel or
+    /// changed the order of its panels. Only jumps right after that slide.
+    #[cfg(feature = "animation")]
+    pub(crate) reordered_pass: Option<u64>,
+    /// Where the content was shown in the previous frame, and whether it moved then.
+    #[cfg(feature = "animation")]
+    pub(crate) content_rect: Option<(Rect, bool)>,
+    /// Offset the content slides back from, and the `InputState::time` the slide started.
+    #[cfg(feature = "animation")]
+    pub(crate) slide: Option<(egui::Vec2, f64)>,
+}
+
+impl DynamicPanelState {
+    fn state_id(id: Id, viewport: ViewportId) -> Id {
+        id.with("__dpanel_state").with(viewport)
+    }
+
+    fn shared_id(id: Id) -> Id {
+        id.with("__dpanel_shared")
+    }
+
+    pub fn load(ctx: &Context, id: Id) -> Self {
+        let viewport = ctx.viewport_id();
+        ctx.data(|d| {
+            let mut state: Self = d.get_temp(Self::state_id(id, viewport)).unwrap_or_default();
+            if let Some(shared) = d.get_temp::<Shared>(Self::shared_id(id)) {
+                shared.choices.apply(&mut state);
+            }
+            state
+        })
+    }
+
+    pub(crate) fn store(self, ctx: &Context, id: Id) {
+        let viewport = ctx.viewport_id();
+        let choices = Choices::of(&self);
+        let repaint = ctx.data_mut(|d| {
+            d.insert_temp(Self::state_id(id, viewport), self);
+            let shared = d.get_temp_mut_or_default::<Shared>(Self::shared_id(id));
+            if !shared.viewports.contains(&viewport) {
+                shared.viewports.push(viewport);
+            }
+            if shared.choices == choices {
+                return Vec::new();
+            }
+            shared.choices = choices;
+            shared.viewports.clone()
+        });
+        for other in repaint.into_iter().filter(|&other| other != viewport) {
+            ctx.request_repaint_of(other);
+        }
+    }
+
+    pub(crate) fn remove(ctx: &Context, id: Id) {
+        ctx.data_mut(|d| {
+            let viewports = d
+                .get_temp::<Shared>(Self::shared_id(id))
+                .map(|shared| shared.viewports)
+                .unwrap_or_default();
+            for viewport in viewports {
+                d.remove::<Self>(Self::state_id(id, viewport));
+            }
+            d.remove::<Shared>(Self::shared_id(id));
+        });
+    }
+
+    /// Whether the panel is expanded, neither collapsed by the user nor hidden by its group.
+    pub fn is_expanded(&self) -> bool {
+        !self.collapsed && !self.auto_hidden
+    }
+
+    /// Index of the config shown last.
+    pub fn current_index(&self) -> Option<usize> {
+        self.index.or(self.last_choice.map(|(_, index)| index))
+    }
+
+    /// Collapsed by the user or the app, not counting being hidden by a group.
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Hidden by a [`crate::PanelGroup`] for lack of space.
+    pub fn is_auto_hidden(&self) -> bool {
+        self.auto_hidden
+    }
+
+    /// Shown in its own window, see [`crate::DynamicPanel::detach`].
+    pub fn is_detached(&self) -> bool {
+        self.detached
+    }
+
+    /// Index pinned with [`crate::DynamicPanel::pin_index`].
+    pub fn pinned_index(&self) -> Option<usize> {
+        self.pinned_index
+    }
+
+    /// A switch between two configs is running, see [`crate::DynamicPanel::animate_index_changes`].
+    pub(crate) fn is_transitioning(&self) -> bool {
+        #[cfg(feature = "animation")]
+        return self.transition.is_some();
+        #[cfg(not(feature = "animation"))]
+        false
+    }
+
+    /// Progress of a running switch between two configs, from 0.0 to 1.0. `None` if there is none.
+    #[cfg(feature = "animation")]
+    pub fn transition_progress(&self, ctx: &Context) -> Option<f32> {
+        self.transition.map(|transition| transition.progress(ctx))
+    }
+}
+
+/// The fields of [`DynamicPanelState`] shared by all viewports.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Choices {
+    collapsed: bool,
+    auto_expanded: bool,
+    user_shown: bool,
+    hidden: bool,
+    flipped: bool,
+    detached: bool,
+    pinned_index: Option<usize>,
+    selection: Option<bool>,
+    linked_selection: Option<bool>,
+}
+
+impl Choices {
+    fn of(state: &DynamicPanelState) -> Self {
+        Self {
+            collapsed: state.collapsed,
+            auto_expanded: state.auto_expanded,
+            user_shown: state.user_shown,
+            hidden: state.hidden,
+            flipped: state.flipped,
+            detached: state.detached,
+            pinned_index: state.pinned_index,
+            selection: state.selection,
+            linked_selection: state.linked_selection,
+        }
+    }
+
+    fn apply(self, state: &mut DynamicPanelState) {
+        state.collapsed = self.collapsed;
+        state.auto_expanded = self.auto_expanded;
+        state.user_shown = self.user_shown;
+        state.hidden = self.hidden;
+        state.flipped = self.flipped;
+        state.detached = self.detached;
+        state.pinned_index = self.pinned_index;
+        state.selection = self.selection;
+        state.linked_selection = self.linked_selection;
+    }
+}
+
+/// The shared part of the state of a panel, and the viewports it was stored from.
+#[derive(Clone, Default)]
+struct Shared {
+    choices: Choices,
+    viewports: Vec<ViewportId>,
+}
+
+/// Animation from the config at `from` to the one at `to`.
+#[cfg(feature = "animation")]
+#[derive(Clone, Copy)]
+pub(crate) struct Transition {
+    pub from: usize,
+    pub to: usize,
+    /// Size of the outgoing panel when the transition started.
+    pub from_size: f32,
+    /// Nominal size of the incoming panel.
+    pub to_size: f32,
+    /// Both configs are on the same side, so the size is interpolated instead of closing and reopening.
+    pub interpolate: bool,
+    /// `InputState::time` when the transition started.
+    pub start_time: f64,
+    /// Number of configs passed on the way, one animation time each. More than one for a
+    /// [chain](crate::DynamicPanel::chain).
+    pub steps: u32,
+}
+
+#[cfg(feature = "animation")]
+impl Transition {
+    /// Size while interpolating between the two configs.
+    pub fn interpolated_size(&self, ctx: &Context) -> f32 {
+        egui::lerp(self.from_size..=self.to_size, self.progress(ctx))
+    }
+
+    /// Progress of the transition, from 0.0 to 1.0.
+    pub fn progress(&self, ctx: &Context) -> f32 {
+        let duration = ctx.style().animation_time * self.steps as f32;
+        if duration <= 0.0 {
+            return 1.0;
+        }
+        let elapsed = ctx.input(|i| i.time) - self.start_time;
+        (elapsed as f32 / duration).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,168 @@
+//! Headless runs of panels at synthetic screen sizes, for regression tests of responsive layouts.
+//!
+//! This takes the place of `egui_kittest` helpers: `egui_kittest` needs egui 0.30 or newer, while this crate is on
+//! egui 0.29, so [`LayoutProbe`] runs a plain [`Context`] instead of a kittest `Harness` and offers no snapshots.
+//!
+//! ```ignore
+//! let mut probe = LayoutProbe::new();
+//! probe.run(egui::vec2(400.0, 800.0), |ctx| {
+//!     sidebar().show_dynamic(ctx, |ui| ui.label("…"));
+//!     egui::CentralPanel::default().show(ctx, |_| {});
+//! });
+//! let shown = probe.shown(&sidebar()).unwrap();
+//! assert_eq!(shown.index, 1);
+//! assert_eq!(shown.rect.height(), 120.0);
+//! ```
+
+use crate::state::DynamicPanelState;
+use crate::DynamicPanel;
+use egui::{Context, Id, RawInput, Rect, Vec2};
+
+/// Which config of a panel was shown and where.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShownPanel {
+    pub index: usize,
+    /// Rect of the docked panel or the overlay's area, in points.
+    pub rect: Rect,
+}
+
+/// What a panel drew in the pass `pass`.
+#[derive(Clone, Copy)]
+struct Drawn {
+    pass: u64,
+    shown: ShownPanel,
+}
+
+fn drawn_id(panel: Id) -> Id {
+    panel.with("__probe_drawn")
+}
+
+/// Remembers that `panel` was drawn at `rect` in the current pass, with the config it is showing.
+pub(crate) fn record_drawn(ctx: &Context, panel: &DynamicPanel<'_>, rect: Rect) {
+    let Some(index) = DynamicPanelState::load(ctx, panel.id()).current_index() else {
+        return;
+    };
+    let drawn = Drawn {
+        pass: ctx.cumulative_pass_nr(),
+        shown: ShownPanel { index, rect },
+    };
+    ctx.data_mut(|d| d.insert_temp(drawn_id(panel.id()), drawn));
+}
+
+/// A context that is run without a window, see the [module docs](self).
+pub struct LayoutProbe {
+    ctx: Context,
+    frames: usize,
+    time: f64,
+    /// Pass number of the last frame of the last run.
+    last_pass: u64,
+}
+
+impl Default for LayoutProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayoutProbe {
+    pub fn new() -> Self {
+        Self {
+            ctx: Context::default(),
+            frames: 4,
+            time: 0.0,
+            last_pass: 0,
+        }
+    }
+
+    /// Number of frames each [`Self::run`] runs, 4 by default. Panels settle on a new config a frame after it was
+    /// chosen, and each frame advances the time by a second so animations are finished.
+    pub fn frames(mut self, frames: usize) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// The context, e.g. to change the style or the panels' state before a run.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Runs `app` on a screen of `size` points.
+    pub fn run(&mut self, size: Vec2, mut app: impl FnMut(&Context)) {
+        for _ in 0..self.frames {
+            self.time += 1.0;
+            self.last_pass = self.ctx.cumulative_pass_nr();
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(egui::Pos2::ZERO, size)),
+                time: Some(self.time),
+                ..Default::default()
+            };
+            let _ = self.ctx.run(input, &mut app);
+        }
+    }
+
+    /// The config `panel` drew in the last frame of the last run and its rect, `None` if it drew nothing, e.g.
+    /// because it is collapsed without a strip. Only panels shown with a `show_dynamic*` method are known.
+    pub fn shown(&self, panel: &DynamicPanel<'_>) -> Option<ShownPanel> {
+        let drawn = self
+            .ctx
+            .data(|d| d.get_temp::<Drawn>(drawn_id(panel.id())))?;
+        (drawn.pass >= self.last_pass).then_some(drawn.shown)
+    }
+
+    /// Panics unless `panel` showed the config at `index` last.
+    #[track_caller]
+    pub fn assert_index(&self, panel: &DynamicPanel<'_>, index: usize) {
+        let shown = self.shown(panel).map(|shown| shown.index);
+        assert_eq!(
+            shown,
+            Some(index),
+            "DynamicPanel `{}` showed config {shown:?} instead of {index}",
+            panel.name()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SinglePanelCfg;
+
+    fn sidebar<'a>() -> DynamicPanel<'a> {
+        DynamicPanel::new("sidebar")
+            .with_panels([
+                SinglePanelCfg::left().exact_size(200.0).into(),
+                SinglePanelCfg::bottom().exact_size(120.0).into(),
+            ])
+            .with_choice_function(|ctx| usize::from(crate::screen_rect(ctx).width() < 600.0))
+    }
+
+    #[test]
+    fn shown_reports_the_drawn_config() {
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            sidebar().show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        let shown = probe.shown(&sidebar()).unwrap();
+        assert_eq!(shown.index, 0);
+        assert_eq!(shown.rect.left(), 0.0);
+        assert_eq!(shown.rect.height(), 600.0);
+
+        probe.run(egui::vec2(400.0, 600.0), |ctx| {
+            sidebar().show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        let shown = probe.shown(&sidebar()).unwrap();
+        assert_eq!(shown.index, 1);
+        assert_eq!(shown.rect.bottom(), 600.0);
+        assert_eq!(shown.rect.width(), 400.0);
+    }
+
+    #[test]
+    fn shown_ignores_panels_not_drawn_in_the_last_run() {
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            sidebar().show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.run(egui::vec2(800.0, 600.0), |_| {});
+        assert_eq!(probe.shown(&sidebar()), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Menu/tool bar with a toggle button per panel, wired to the collapse state of [`DynamicPanel`].
+
+use crate::state::DynamicPanelState;
+use crate::DynamicPanel;
+use egui::{Context, Id, InnerResponse, TopBottomPanel, Ui, WidgetText};
+
+struct PanelToggle<'p, 'a> {
+    panel: &'p DynamicPanel<'a>,
+    icon: WidgetText,
+    tooltip: WidgetText,
+}
+
+/// Row of toggle buttons, one per registered panel. A button is shown pressed while its panel is expanded and
+/// toggles it when clicked. Panels have to be shown with [`DynamicPanel::show_dynamic_collapsible`] (or read
+/// [`DynamicPanel::is_expanded`] themselves) for the buttons to have an effect.
+///
+/// The buttons win over a [`crate::PanelGroup`]: a panel the group hid for lack of space is shown again when its
+/// button is clicked, and the group hides other panels instead until it is collapsed.
+pub struct PanelToggleBar<'p, 'a> {
+    id: Id,
+    toggles: Vec<PanelToggle<'p, 'a>>,
+}
+
+impl<'p, 'a> PanelToggleBar<'p, 'a> {
+    /// Constructor. The id is used for the top panel created by [`Self::show`].
+    pub fn new(id: impl Into<Id>) -> Self {
+        Self {
+            id: id.into(),
+            toggles: vec![],
+        }
+    }
+
+    /// Adds a toggle button for `panel`, labelled with `icon` (e.g. an emoji) and described by `tooltip` on hover.
+    pub fn with_panel(
+        mut self,
+        panel: &'p DynamicPanel<'a>,
+        icon: impl Into<WidgetText>,
+        tooltip: impl Into<WidgetText>,
+    ) -> Self {
+        self.toggles.push(PanelToggle {
+            panel,
+            icon: icon.into(),
+            tooltip: tooltip.into(),
+        });
+        self
+    }
+
+    /// Shows the bar as its own top panel. Has to be called before the panels it toggles, like any top panel.
+    pub fn show(self, ctx: &Context) -> InnerResponse<bool> {
+        let id = self.id;
+        TopBottomPanel::top(id).show(ctx, |ui| egui::menu::bar(ui, |ui| self.ui(ui)).inner)
+    }
+
+    /// Adds the buttons to an existing Ui, e.g. next to the entries of an app's own menu bar.
+    /// Returns whether any panel was toggled.
+    pub fn ui(self, ui: &mut Ui) -> bool {
+        let ctx = ui.ctx().clone();
+        let mut toggled = false;
+        ui.horizontal(|ui| {
+            for toggle in self.toggles {
+                let expanded = toggle.panel.is_expanded(&ctx);
+                let response = ui
+                    .selectable_label(expanded, toggle.icon)
+                    .on_hover_text(toggle.tooltip);
+                if response.clicked() {
+                    toggle.panel.set_expanded(&ctx, !expanded);
+                    let id = toggle.panel.id();
+                    let mut state = DynamicPanelState::load(&ctx, id);
+                    state.user_shown = !expanded;
+                    state.store(&ctx, id);
+                    toggled = true;
+                }
+            }
+        });
+        toggled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SinglePanelCfg;
+    use egui::containers::panel::PanelState;
+    use egui::{Event, PointerButton, RawInput, Rect};
+
+    /// Whether the sidebar is expanded after each of `clicks` clicks on its toggle button.
+    fn expanded_after_clicks(clicks: usize) -> Vec<bool> {
+        let ctx = Context::default();
+        let sidebar = DynamicPanel::new("sidebar")
+            .with_panels([SinglePanelCfg::left().into()])
+            .with_choice_function(|_| 0);
+        let run = |events: Vec<Event>| {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(800.0, 600.0),
+                )),
+                events,
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                PanelToggleBar::new("bar")
+                    .with_panel(&sidebar, "☰", "Sidebar")
+                    .show(ctx);
+            });
+        };
+        run(vec![]);
+        let bar = PanelState::load(&ctx, Id::new("bar")).unwrap().rect;
+        let button = egui::pos2(bar.left() + 12.0, bar.center().y);
+        (0..clicks)
+            .map(|_| {
+                let press = |pressed| Event::PointerButton {
+                    pos: button,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Default::default(),
+                };
+                run(vec![Event::PointerMoved(button), press(true)]);
+                run(vec![press(false)]);
+                sidebar.is_expanded(&ctx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn buttons_toggle_their_panel() {
+        assert_eq!(expanded_after_clicks(2), [false, true]);
+    }
+}
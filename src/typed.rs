@@ -0,0 +1,207 @@
+//! Builders for [`SinglePanelCfg`] that only offer the fields of their axis.
+//!
+//! `SinglePanelCfg` has width and height fields for all sides and ignores the ones that don't fit its side. Configs
+//! built with [`SideCfg`] and [`TopBottomCfg`] can't set them in the first place, so `min_height` on a left panel
+//! is a compile error instead of a no-op.
+
+use crate::{BackgroundFit, PanelCfg, SafeArea, Side, SinglePanelCfg};
+use egui::{Frame, SidePanel, TopBottomPanel};
+
+/// The setters that apply to both axes, and the conversions into the untyped configs.
+macro_rules! shared_setters {
+    ($cfg:ident) => {
+        impl $cfg {
+            pub fn resizable(mut self, resizable: bool) -> Self {
+                self.0.resizable = Some(resizable);
+                self
+            }
+
+            pub fn show_separator_line(mut self, show: bool) -> Self {
+                self.0.show_separator_line = Some(show);
+                self
+            }
+
+            pub fn frame(mut self, frame: Frame) -> Self {
+                self.0.frame = Some(frame);
+                self
+            }
+
+            /// See [`SinglePanelCfg::resize_grab_radius`](SinglePanelCfg#structfield.resize_grab_radius).
+            pub fn resize_grab_radius(mut self, radius: f32) -> Self {
+                self.0.resize_grab_radius = Some(radius);
+                self
+            }
+
+            /// See [`SinglePanelCfg::keyboard_resize_step`](SinglePanelCfg#structfield.keyboard_resize_step).
+            pub fn keyboard_resize_step(mut self, step: f32) -> Self {
+                self.0.keyboard_resize_step = Some(step);
+                self
+            }
+
+            pub fn safe_area(self, safe_area: SafeArea) -> Self {
+                Self(self.0.safe_area(safe_area))
+            }
+
+            pub fn touch_friendly(self) -> Self {
+                Self(self.0.touch_friendly())
+            }
+
+            /// See [`SinglePanelCfg::background`].
+            pub fn background(
+                self,
+                image: impl Into<egui::ImageSource<'static>>,
+                fit: BackgroundFit,
+            ) -> Self {
+                Self(self.0.background(image, fit))
+            }
+
+            /// See [`SinglePanelCfg::size`](SinglePanelCfg#structfield.size).
+            #[cfg(feature = "egui_extras")]
+            pub fn size(self, size: egui_extras::Size) -> Self {
+                Self(self.0.size(size))
+            }
+
+            pub fn side(&self) -> Side {
+                self.0.side
+            }
+        }
+
+        impl From<$cfg> for SinglePanelCfg {
+            fn from(cfg: $cfg) -> Self {
+                cfg.0
+            }
+        }
+
+        impl From<$cfg> for PanelCfg {
+            fn from(cfg: $cfg) -> Self {
+                PanelCfg::Single(cfg.0)
+            }
+        }
+    };
+}
+
+/// Builder for a [`SinglePanelCfg`] on the left or right, with only width fields.
+///
+/// ```ignore
+/// let cfg: PanelCfg = SideCfg::left().width_range(150.0, 400.0).resizable(true).into();
+/// ```
+#[derive(Clone)]
+pub struct SideCfg(SinglePanelCfg);
+
+impl SideCfg {
+    pub fn left() -> Self {
+        Self(SinglePanelCfg::left())
+    }
+
+    pub fn right() -> Self {
+        Self(SinglePanelCfg::right())
+    }
+
+    pub fn default_width(mut self, width: f32) -> Self {
+        self.0.default_width = Some(width);
+        self
+    }
+
+    pub fn min_width(mut self, width: f32) -> Self {
+        self.0.min_width = Some(width);
+        self
+    }
+
+    pub fn max_width(mut self, width: f32) -> Self {
+        self.0.max_width = Some(width);
+        self
+    }
+
+    pub fn width_range(mut self, min: f32, max: f32) -> Self {
+        self.0.width_range = Some((min, max));
+        self
+    }
+
+    pub fn exact_width(mut self, width: f32) -> Self {
+        self.0.exact_width = Some(width);
+        self
+    }
+
+    /// Customize the underlying `SidePanel` after all other fields were applied.
+    pub fn map(self, f: impl Fn(SidePanel) -> SidePanel + Send + Sync + 'static) -> Self {
+        Self(self.0.map_side_panel(f))
+    }
+}
+
+shared_setters!(SideCfg);
+
+/// Builder for a [`SinglePanelCfg`] at the top or bottom, with only height fields.
+#[derive(Clone)]
+pub struct TopBottomCfg(SinglePanelCfg);
+
+impl TopBottomCfg {
+    pub fn top() -> Self {
+        Self(SinglePanelCfg::top())
+    }
+
+    pub fn bottom() -> Self {
+        Self(SinglePanelCfg::bottom())
+    }
+
+    pub fn default_height(mut self, height: f32) -> Self {
+        self.0.default_height = Some(height);
+        self
+    }
+
+    pub fn min_height(mut self, height: f32) -> Self {
+        self.0.min_height = Some(height);
+        self
+    }
+
+    pub fn max_height(mut self, height: f32) -> Self {
+        self.0.max_height = Some(height);
+        self
+    }
+
+    pub fn height_range(mut self, min: f32, max: f32) -> Self {
+        self.0.height_range = Some((min, max));
+        self
+    }
+
+    pub fn exact_height(mut self, height: f32) -> Self {
+        self.0.exact_height = Some(height);
+        self
+    }
+
+    /// Customize the underlying `TopBottomPanel` after all other fields were applied.
+    pub fn map(self, f: impl Fn(TopBottomPanel) -> TopBottomPanel + Send + Sync + 'static) -> Self {
+        Self(self.0.map_top_bottom(f))
+    }
+}
+
+shared_setters!(TopBottomCfg);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LayoutProbe;
+    use crate::DynamicPanel;
+    use egui::Rect;
+
+    /// Rect of a panel showing `cfg` on a screen of 800x600 points.
+    fn shown_rect(cfg: impl Into<PanelCfg> + Clone) -> Rect {
+        fn panel<'a>(cfg: PanelCfg) -> DynamicPanel<'a> {
+            DynamicPanel::new("panel")
+                .with_panels([cfg])
+                .with_choice_function(|_| 0)
+        }
+        let mut probe = LayoutProbe::new();
+        probe.run(egui::vec2(800.0, 600.0), |ctx| {
+            panel(cfg.clone().into()).show_dynamic(ctx, |ui| ui.label("…"));
+        });
+        probe.shown(&panel(cfg.into())).unwrap().rect
+    }
+
+    #[test]
+    fn sizes_apply_to_the_axis_of_the_side() {
+        let rect = shown_rect(SideCfg::right().exact_width(250.0));
+        assert_eq!((rect.width(), rect.right()), (250.0, 800.0));
+        let rect = shown_rect(TopBottomCfg::bottom().exact_height(80.0));
+        assert_eq!((rect.height(), rect.bottom()), (80.0, 600.0));
+    }
+}
@@ -0,0 +1,199 @@
+//! Layout state in the URL fragment, so a shared link of a web app reproduces the same panel arrangement.
+//!
+//! Every panel is stored as `dp.<name>=<index>`, followed by `c` if it is collapsed, e.g.
+//! `#dp.sidebar=1&dp.inspector=0c`. Entries of the fragment that don't start with `dp.` are left alone.
+//! Restoring shows the stored index until the choice function switches to another config, e.g. when the window
+//! crosses a breakpoint. Indices without a config are ignored.
+
+use crate::state::DynamicPanelState;
+use crate::DynamicPanel;
+use egui::Context;
+
+const PREFIX: &str = "dp.";
+
+/// Encodes index and collapse state of `panels`, without the leading `#`.
+pub fn encode(ctx: &Context, panels: &[&DynamicPanel]) -> String {
+    panels
+        .iter()
+        .filter_map(|panel| {
+            let index = panel.current_index(ctx)?;
+            let collapsed = if DynamicPanelState::load(ctx, panel.id()).collapsed {
+                "c"
+            } else {
+                ""
+            };
+            Some(format!(
+                "{PREFIX}{}={index}{collapsed}",
+                escape(panel.name())
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Restores the state of `panels` from a fragment written by [`encode`]. Panels missing from the fragment are
+/// not touched. The leading `#` is optional. A fragment with a malformed entry of this module is ignored
+/// entirely, so a mangled link doesn't restore half a layout.
+pub fn decode(ctx: &Context, panels: &[&DynamicPanel], fragment: &str) {
+    let mut restored = vec![];
+    for entry in fragment.trim_start_matches('#').split('&') {
+        let Some(entry) = entry.strip_prefix(PREFIX) else {
+            continue;
+        };
+        let Some((name, value)) = entry.split_once('=') else {
+            log::warn!("Ignoring the URL fragment, `{PREFIX}{entry}` has no value");
+            return;
+        };
+        let (index, collapsed) = match value.strip_suffix('c') {
+            Some(index) => (index, true),
+            None => (value, false),
+        };
+        let Ok(index) = index.parse::<usize>() else {
+            log::warn!("Ignoring the URL fragment, `{PREFIX}{entry}` has no valid index");
+            return;
+        };
+        if let Some(panel) = panels.iter().find(|p| escape(p.name()) == name) {
+            restored.push((panel, index, collapsed));
+        }
+    }
+    for (panel, index, collapsed) in restored {
+        panel.prefer_index(ctx, index);
+        let mut state = DynamicPanelState::load(ctx, panel.id());
+        state.collapsed = collapsed;
+        state.store(ctx, panel.id());
+    }
+}
+
+/// `fragment` with the entries of this module replaced by the current state of `panels`.
+pub fn merge(ctx: &Context, panels: &[&DynamicPanel], fragment: &str) -> String {
+    let mut entries: Vec<String> = fragment
+        .trim_start_matches('#')
+        .split('&')
+        .filter(|entry| !entry.is_empty() && !entry.starts_with(PREFIX))
+        .map(str::to_owned)
+        .collect();
+    let encoded = encode(ctx, panels);
+    if !encoded.is_empty() {
+        entries.push(encoded);
+    }
+    entries.join("&")
+}
+
+/// Writes the state of `panels` to the fragment of the page's URL.
+#[cfg(target_arch = "wasm32")]
+pub fn write_to_url(ctx: &Context, panels: &[&DynamicPanel]) {
+    let Some(location) = web_sys::window().map(|w| w.location()) else {
+        return;
+    };
+    let current = location.hash().unwrap_or_default();
+    let hash = merge(ctx, panels, &current);
+    if hash != current.trim_start_matches('#') {
+        let _ = location.set_hash(&hash);
+    }
+}
+
+/// Restores the state of `panels` from the fragment of the page's URL, e.g. once on startup.
+#[cfg(target_arch = "wasm32")]
+pub fn restore_from_url(ctx: &Context, panels: &[&DynamicPanel]) {
+    if let Some(hash) = web_sys::window().and_then(|w| w.location().hash().ok()) {
+        decode(ctx, panels, &hash);
+    }
+}
+
+/// Percent-encodes everything but ASCII alphanumerics and `-_.`, which keeps `=` and `&` out of names.
+fn escape(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LayoutProbe;
+    use crate::SinglePanelCfg;
+
+    fn panel<'a>(name: &str) -> DynamicPanel<'a> {
+        DynamicPanel::new(name)
+            .with_panels([
+                SinglePanelCfg::left().into(),
+                SinglePanelCfg::right().into(),
+            ])
+            .with_choice_function(|_| 0)
+    }
+
+    /// Runs `app` for one frame.
+    fn run(app: impl FnMut(&Context)) {
+        LayoutProbe::new()
+            .frames(1)
+            .run(egui::vec2(800.0, 600.0), app);
+    }
+
+    #[test]
+    fn encode_writes_index_collapse_state_and_escaped_names() {
+        run(|ctx| {
+            let (sidebar, inspector) = (panel("sidebar"), panel("the inspector"));
+            sidebar.show_dynamic(ctx, |_| {});
+            inspector.show_dynamic(ctx, |_| {});
+            inspector.set_expanded(ctx, false);
+            assert_eq!(
+                encode(ctx, &[&sidebar, &inspector]),
+                "dp.sidebar=0&dp.the%20inspector=0c"
+            );
+        });
+    }
+
+    #[test]
+    fn decode_restores_the_encoded_state_once() {
+        run(|ctx| {
+            let sidebar = panel("sidebar");
+            decode(ctx, &[&sidebar], "#other=1&dp.sidebar=1c");
+            sidebar.show_dynamic(ctx, |_| {});
+            assert_eq!(sidebar.current_index(ctx), Some(1));
+            assert!(DynamicPanelState::load(ctx, sidebar.id()).collapsed);
+            assert_eq!(
+                merge(ctx, &[&sidebar], "#other=1&dp.sidebar=0"),
+                "other=1&dp.sidebar=1c"
+            );
+            // Not pinned: the stored index stays until the choice function changes its mind.
+            assert_eq!(
+                DynamicPanelState::load(ctx, sidebar.id()).pinned_index,
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn decode_ignores_indices_without_config() {
+        run(|ctx| {
+            let sidebar = panel("sidebar");
+            decode(ctx, &[&sidebar], "dp.sidebar=5");
+            sidebar.show_dynamic(ctx, |_| {});
+            assert_eq!(sidebar.current_index(ctx), Some(0));
+        });
+    }
+
+    #[test]
+    fn decode_ignores_malformed_fragments_entirely() {
+        run(|ctx| {
+            let (sidebar, inspector) = (panel("sidebar"), panel("inspector"));
+            decode(
+                ctx,
+                &[&sidebar, &inspector],
+                "dp.sidebar=1c&dp.inspector=xc",
+            );
+            decode(ctx, &[&sidebar, &inspector], "dp.sidebar=1c&dp.inspector");
+            sidebar.show_dynamic(ctx, |_| {});
+            assert_eq!(sidebar.current_index(ctx), Some(0));
+            for panel in [&sidebar, &inspector] {
+                assert!(!DynamicPanelState::load(ctx, panel.id()).collapsed);
+            }
+        });
+    }
+}